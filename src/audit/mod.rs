@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use crate::deck::{Deck, ShuffleOptions};
+
+/// P-values below this are reported as suspicious.
+pub const SUSPICIOUS_P: f64 = 0.001;
+
+/// How often one card landed in each position, and how often it was reversed.
+#[derive(PartialEq, Debug)]
+pub struct CardAudit {
+    pub name: String,
+    pub positions: Vec<u32>,
+    pub reversed: u32,
+}
+
+impl CardAudit {
+    pub fn draws(&self) -> u32 {
+        self.positions.iter().sum()
+    }
+}
+
+/// Counts from dealing many shuffled decks, with the statistics to judge
+/// whether any card or position is favoured.
+#[derive(PartialEq, Debug)]
+pub struct AuditReport {
+    pub runs: usize,
+    pub reversal_chance: f64,
+    pub cards: Vec<CardAudit>,
+}
+
+/// Shuffles a fresh copy of the deck for each of `runs` seeds starting at
+/// `seed` and deals `positions` cards, skipping any jumpers.
+pub fn audit(deck: &Deck, runs: usize, positions: usize, seed: u64, options: &ShuffleOptions) -> AuditReport {
+    let mut cards: Vec<CardAudit> = deck.cards().iter()
+        .map(|card| CardAudit { name: card.name().to_string(), positions: vec![0; positions], reversed: 0 })
+        .collect();
+    let index: HashMap<String, usize> = cards.iter().enumerate().map(|(i, card)| (card.name.clone(), i)).collect();
+    for run in 0..runs {
+        let mut shuffled = deck.clone();
+        let jumpers = shuffled.shuffle_to_deal(seed.wrapping_add(run as u64), options, positions);
+        for (position, card) in shuffled.draw(jumpers + positions)[jumpers..].iter().enumerate() {
+            let audit = &mut cards[index[card.name()]];
+            audit.positions[position] += 1;
+            if card.is_reversed() {
+                audit.reversed += 1;
+            }
+        }
+    }
+    AuditReport { runs, reversal_chance: options.reversal_chance, cards }
+}
+
+/// Chance of a chi-square statistic at least this large with `df` degrees of
+/// freedom, by the Wilson-Hilferty approximation.
+pub fn chi_square_p(statistic: f64, df: usize) -> f64 {
+    let df = df as f64;
+    let z = ((statistic / df).cbrt() - (1.0 - 2.0 / (9.0 * df))) / (2.0 / (9.0 * df)).sqrt();
+    0.5 * erfc(z / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function, after Abramowitz and Stegun 7.1.26.
+fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        return 2.0 - erfc(-x);
+    }
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t * (0.254_829_592 + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    poly * (-x * x).exp()
+}
+
+impl AuditReport {
+    pub fn positions(&self) -> usize {
+        self.cards.first().map_or(0, |card| card.positions.len())
+    }
+
+    fn expected(&self) -> f64 {
+        self.runs as f64 / self.cards.len() as f64
+    }
+
+    /// Chi-square statistic for how evenly the cards landed in one position.
+    pub fn position_chi_square(&self, position: usize) -> f64 {
+        let expected = self.expected();
+        self.cards.iter()
+            .map(|card| (card.positions[position] as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    pub fn position_df(&self) -> usize {
+        self.cards.len().saturating_sub(1)
+    }
+
+    pub fn reversal_rate(&self) -> f64 {
+        let draws: u32 = self.cards.iter().map(|card| card.draws()).sum();
+        match draws {
+            0 => 0.0,
+            draws => self.cards.iter().map(|card| card.reversed).sum::<u32>() as f64 / draws as f64,
+        }
+    }
+
+    pub fn print(&self, mut writer: impl std::io::Write) {
+        writeln!(writer, "Shuffle Audit").unwrap();
+        writeln!(writer, "=============").unwrap();
+        writeln!(
+            writer,
+            "{} shuffles of {} cards, {} positions dealt, {:.1} expected per card and position",
+            self.runs,
+            self.cards.len(),
+            self.positions(),
+            self.expected()
+            ).unwrap();
+        writeln!(writer).unwrap();
+        writeln!(writer, "{:<14}{:>12}{:>10}", "Position", "Chi-square", "p-value").unwrap();
+        let df = self.position_df();
+        for position in 0..self.positions() {
+            let statistic = self.position_chi_square(position);
+            self.print_row(&format!("{}", position + 1), statistic, chi_square_p(statistic, df), &mut writer);
+        }
+        writeln!(writer).unwrap();
+
+        writeln!(
+            writer,
+            "Reversals: {:.2}% of draws, {:.2}% expected",
+            self.reversal_rate() * 100.0,
+            self.reversal_chance * 100.0
+            ).unwrap();
+        let rates: Vec<(f64, &CardAudit)> = self.cards.iter()
+            .filter(|card| card.draws() > 0)
+            .map(|card| (card.reversed as f64 / card.draws() as f64, card))
+            .collect();
+        let lowest = rates.iter().min_by(|a, b| a.0.total_cmp(&b.0));
+        let highest = rates.iter().max_by(|a, b| a.0.total_cmp(&b.0));
+        if let (Some((low, low_card)), Some((high, high_card))) = (lowest, highest) {
+            writeln!(
+                writer,
+                "Lowest card rate {:.2}% ({}), highest {:.2}% ({})",
+                low * 100.0,
+                low_card.name,
+                high * 100.0,
+                high_card.name
+                ).unwrap();
+        }
+    }
+
+    fn print_row(&self, label: &str, statistic: f64, p: f64, mut writer: impl std::io::Write) {
+        let flag = match p < SUSPICIOUS_P {
+            true => "  suspicious",
+            false => "",
+        };
+        writeln!(writer, "{:<14}{:>12.2}{:>10.4}{}", label, statistic, p, flag).unwrap();
+    }
+
+    /// Every card's count in each position and its reversal rate.
+    pub fn print_table(&self, mut writer: impl std::io::Write) {
+        let width = self.cards.iter().map(|card| card.name.len()).max().unwrap_or(0);
+        let header: String = (1..=self.positions()).map(|position| format!("{:>8}", position)).collect();
+        writeln!(writer, "{:<width$}{}{:>10}", "Card", header, "Reversed", width = width).unwrap();
+        for card in &self.cards {
+            let counts: String = card.positions.iter().map(|count| format!("{:>8}", count)).collect();
+            let reversed = match card.draws() {
+                0 => 0.0,
+                draws => card.reversed as f64 / draws as f64 * 100.0,
+            };
+            writeln!(writer, "{:<width$}{}{:>9.2}%", card.name, counts, reversed, width = width).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::audit::{audit, chi_square_p};
+    use crate::deck::{Deck, ShuffleOptions};
+    use crate::deck::test_utils::utils::return_test_deck;
+    use crate::stored_element::StoredElement;
+
+    #[test]
+    fn chi_square_p_values() {
+        assert!((chi_square_p(18.307, 10) - 0.05).abs() < 0.002);
+        assert!((chi_square_p(9.342, 10) - 0.5).abs() < 0.01);
+        assert!(chi_square_p(100.0, 10) < 0.000_001);
+    }
+
+    #[test]
+    fn audit_shuffles() {
+        let deck = Deck::new_from_json(&return_test_deck());
+        let options = ShuffleOptions { reversal_chance: 0.25, ..ShuffleOptions::default() };
+        let report = audit(&deck, 3000, 2, 1, &options);
+        assert_eq!(report.positions(), 2);
+        assert_eq!(report.cards.iter().map(|card| card.draws()).sum::<u32>(), 6000);
+        for position in 0..2 {
+            assert!(chi_square_p(report.position_chi_square(position), report.position_df()) > 0.001);
+        }
+        assert!((report.reversal_rate() - 0.25).abs() < 0.02);
+
+        let unshuffled = ShuffleOptions { passes: 0, cut: Some(1), ..ShuffleOptions::default() };
+        let report = audit(&deck, 300, 1, 1, &unshuffled);
+        assert_eq!(report.cards[1].positions, vec![300]);
+        assert!(chi_square_p(report.position_chi_square(0), report.position_df()) < 0.001);
+
+        let mut test_result = Vec::new();
+        report.print(&mut test_result);
+        let output = String::from_utf8(test_result).unwrap();
+        assert!(output.contains("\n1                   600.00    0.0000  suspicious\n\nReversals"));
+
+        let mut test_result = Vec::new();
+        report.print_table(&mut test_result);
+        assert_eq!(String::from_utf8(test_result).unwrap(), "Card             1  Reversed
+test_name        0     0.00%
+test_name1     300     0.00%
+test_name2       0     0.00%
+");
+    }
+}
@@ -1,16 +1,29 @@
 extern crate clap;
 extern crate pager;
 extern crate shellexpand;
-use clap::{App, Arg};
+use clap::{App, AppSettings, Arg, SubCommand};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use pager::Pager;
+use dialoguer::Select;
 
 extern crate terminal_tarot;
-use terminal_tarot::deck::Deck;
-use terminal_tarot::spread::{Spread, FilledSpread};
-use terminal_tarot::stored_element::StoredElement;
-use terminal_tarot::default_files::{write_default_files, ElementType};
+use terminal_tarot::deck::{Deck, MeaningHistory, MeaningStrategy, ShuffleMethod, ShuffleOptions};
+use terminal_tarot::spread::{Spread, FilledSpread, ReadingOptions};
+use terminal_tarot::stored_element::{SavedState, StoredElement};
+use terminal_tarot::default_files::{write_default_files, data_file_path, ElementType};
+use terminal_tarot::journal::{append_reading, load_readings, JOURNAL_FILENAME};
+use terminal_tarot::combinations::load_combinations;
+use terminal_tarot::daily::{deck_fingerprint, DailyCard, DAILY_FILENAME};
+use terminal_tarot::numerology::{birth_cards, year_card};
+use terminal_tarot::search::{keyword_index, print_hits, search};
+use terminal_tarot::study::{Focus, Grade, Question, StudyState, STUDY_FILENAME};
+use terminal_tarot::journey::{Journey, JourneyProgress, PROGRESS_FILENAME};
+use terminal_tarot::oracle::OracleReading;
+use terminal_tarot::audit::audit;
+use terminal_tarot::probability::{describe_chance, exact, simulate, Event, Query};
+use terminal_tarot::entropy::{mix_timings, os_seed, ritual_timings, EntropySource, RITUAL_PRESSES};
+use terminal_tarot::calendar::{decan_calendar, print_calendar, ruling_card, write_ics};
 
 fn main() {
     let now = std::time::SystemTime::now();
@@ -24,7 +37,7 @@ fn main() {
         .arg(Arg::with_name("detailed")
              .short("d")
              .long("detailed")
-             .help("Should multiple interpretations for cards/positions be displayed?")
+             .help("Should multiple interpretations for cards/positions be displayed? Same as --meanings all")
              )
         .arg(Arg::with_name("seed")
              .short("s")
@@ -32,9 +45,28 @@ fn main() {
              .takes_value(true)
              .help("Value used to draw cards and select interpretations")
              )
+        .arg(Arg::with_name("entropy")
+             .long("entropy")
+             .takes_value(true)
+             .possible_values(&["seed", "os", "ritual"])
+             .help("Where the randomness comes from: the seed (or current time), the operating system, or the timing of your own shuffling")
+             )
+        .arg(Arg::with_name("replay")
+             .long("replay")
+             .takes_value(true)
+             .conflicts_with_all(&["seed", "entropy"])
+             .help("Repeat an earlier reading from the seed number it printed or saved in the journal")
+             )
+        .arg(Arg::with_name("question")
+             .short("q")
+             .long("question")
+             .takes_value(true)
+             .help("Question to ask the cards. Shown above the reading, saved with it and mixed into the seed unless replaying")
+             )
         .arg(Arg::with_name("overwrite_default_files")
              .short("o")
              .long("overwrite")
+             .global(true)
              .help("Write packaged spread/deck files to default directory ($HOME/.local/share/terminal_tarot)")
              )
         .arg(Arg::with_name("spread_path")
@@ -45,56 +77,828 @@ fn main() {
         .arg(Arg::with_name("deck_path")
              .long("deck_path")
              .takes_value(true)
+             .global(true)
              .help("Path that holds desired deck files. Can be a single file or a directory")
              )
+        .arg(Arg::with_name("shuffle")
+             .long("shuffle")
+             .takes_value(true)
+             .possible_values(&["random", "riffle", "overhand", "pile"])
+             .default_value("random")
+             .help("Method used to shuffle the deck")
+             )
+        .arg(Arg::with_name("passes")
+             .long("passes")
+             .takes_value(true)
+             .default_value("1")
+             .help("Number of times the shuffle method is repeated")
+             )
+        .arg(Arg::with_name("cut")
+             .long("cut")
+             .takes_value(true)
+             .help("Cut the deck at this card position after shuffling")
+             )
+        .arg(Arg::with_name("jumpers")
+             .long("jumpers")
+             .takes_value(true)
+             .help("Chance (0 to 1) that cards jump out of the deck while shuffling")
+             )
+        .arg(Arg::with_name("save")
+             .long("save")
+             .help("Record the reading in the journal ($HOME/.local/share/terminal_tarot/journal.jsonl)")
+             )
+        .arg(Arg::with_name("shadow_card")
+             .long("shadow")
+             .help("Also show the shadow card left at the bottom of the deck")
+             )
+        .arg(Arg::with_name("remaining_summary")
+             .long("remaining")
+             .help("Summarize the major arcana and suits left in the deck after the reading")
+             )
+        .arg(Arg::with_name("reversals")
+             .long("reversals")
+             .takes_value(true)
+             .help("Chance (0 to 1) that each card is turned upside down while shuffling")
+             )
+        .arg(Arg::with_name("summary")
+             .long("summary")
+             .help("Finish the reading with a count of arcana, suits, court cards, reversals and repeated numbers")
+             )
+        .arg(Arg::with_name("timing")
+             .long("timing")
+             .help("Estimate when the matter will come about from the suits and numbers of the cards drawn")
+             )
+        .arg(Arg::with_name("dignities")
+             .long("dignities")
+             .help("Show the elemental dignities of each card relative to its neighbouring positions")
+             )
+        .arg(Arg::with_name("combinations_path")
+             .long("combinations_path")
+             .takes_value(true)
+             .help("Path that holds card combination files. Can be a single file or a directory")
+             )
+        .arg(Arg::with_name("narrative")
+             .long("narrative")
+             .help("Tell the reading as flowing prose using the spread's story template")
+             )
+        .arg(Arg::with_name("template")
+             .long("template")
+             .takes_value(true)
+             .help("Handlebars template file, or directory of card/position/reading templates, used to print the reading")
+             )
+        .arg(Arg::with_name("correspondences")
+             .long("correspondences")
+             .help("Show each card's element, planet, zodiac sign, decan, Hebrew letter and Tree of Life path")
+             )
+        .arg(Arg::with_name("oneline")
+             .long("oneline")
+             .global(true)
+             .conflicts_with_all(&["interactive", "save"])
+             .help("Draw a single card and print it on one line without the pager, for shell startup files and status bars")
+             )
+        .arg(Arg::with_name("meanings")
+             .long("meanings")
+             .takes_value(true)
+             .possible_values(&["random", "first", "rotating", "weighted", "all"])
+             .conflicts_with("detailed")
+             .help("How card meanings are chosen; overrides the spread's own setting. Rotating uses saved readings")
+             )
+        .subcommand(SubCommand::with_name("daily")
+             .about("Draw the card of the day. The same card is shown all day and cached for quick startup")
+             .arg(Arg::with_name("key")
+                  .long("key")
+                  .takes_value(true)
+                  .help("Personal key mixed into the date so each user gets their own card")
+                  )
+             )
+        .subcommand(SubCommand::with_name("card")
+             .about("Look up a card in the deck by name, or list the deck")
+             .setting(AppSettings::ArgRequiredElseHelp)
+             .setting(AppSettings::ArgsNegateSubcommands)
+             .arg(Arg::with_name("name")
+                  .multiple(true)
+                  .conflicts_with("list")
+                  .help("Name of the card. Close matches such as \"2 of pentacles\" or \"hierophant\" are found too")
+                  )
+             .arg(Arg::with_name("list")
+                  .long("list")
+                  .help("List every card in the deck, grouped by suit")
+                  )
+             .subcommand(SubCommand::with_name("info")
+                  .about("Show every meaning, keyword and correspondence of a card")
+                  .arg(Arg::with_name("name")
+                       .required(true)
+                       .multiple(true)
+                       .help("Name of the card, e.g. \"ace of cups\"")
+                       )
+                  )
+             )
+        .subcommand(SubCommand::with_name("study")
+             .about("Quiz yourself on the deck with flashcards scheduled by spaced repetition")
+             .arg(Arg::with_name("focus")
+                  .long("focus")
+                  .takes_value(true)
+                  .default_value("all")
+                  .help("Study only one suit, e.g. \"cups\", or \"major\" for the major arcana")
+                  )
+             .arg(Arg::with_name("new")
+                  .long("new")
+                  .takes_value(true)
+                  .default_value("10")
+                  .help("Most cards never studied before to add to the session")
+                  )
+             )
+        .subcommand(SubCommand::with_name("journey")
+             .about("Walk through the major arcana in order as the Fool's Journey, resuming where you left off")
+             .arg(Arg::with_name("journey_path")
+                  .long("journey_path")
+                  .takes_value(true)
+                  .help("Path that holds journey files. Can be a single file or a directory")
+                  )
+             .arg(Arg::with_name("restart")
+                  .long("restart")
+                  .help("Start the journey again from the beginning")
+                  )
+             )
+        .subcommand(SubCommand::with_name("search")
+             .about("Find cards whose keywords, fortunes or meanings mention a term, across every deck in the deck path")
+             .arg(Arg::with_name("term")
+                  .multiple(true)
+                  .required_unless("index")
+                  .help("Word or phrase to look for")
+                  )
+             .arg(Arg::with_name("keyword")
+                  .long("keyword")
+                  .help("Only list cards that have the term as one of their keywords")
+                  )
+             .arg(Arg::with_name("index")
+                  .long("index")
+                  .conflicts_with_all(&["term", "keyword"])
+                  .help("List every keyword with the cards that carry it")
+                  )
+             )
+        .subcommand(SubCommand::with_name("decan")
+             .about("Show the minor arcana card whose decan rules a date, or list the decans of a year")
+             .arg(Arg::with_name("date")
+                  .long("date")
+                  .takes_value(true)
+                  .conflicts_with("calendar")
+                  .help("Date as YYYY-MM-DD. Defaults to today")
+                  )
+             .arg(Arg::with_name("calendar")
+                  .long("calendar")
+                  .help("List every decan card across the year")
+                  )
+             .arg(Arg::with_name("year")
+                  .long("year")
+                  .takes_value(true)
+                  .requires("calendar")
+                  .help("Year to list. Defaults to the current year")
+                  )
+             .arg(Arg::with_name("ics")
+                  .long("ics")
+                  .requires("calendar")
+                  .help("Write the calendar in iCalendar format for import into calendar applications")
+                  )
+             )
+        .subcommand(SubCommand::with_name("numerology")
+             .about("Find major arcana cards from birth dates, years and readings")
+             .setting(AppSettings::SubcommandRequiredElseHelp)
+             .subcommand(SubCommand::with_name("birth")
+                  .about("Birth cards for a birth date")
+                  .arg(Arg::with_name("date")
+                       .required(true)
+                       .help("Birth date as YYYY-MM-DD")
+                       )
+                  )
+             .subcommand(SubCommand::with_name("year")
+                  .about("Personal year card for a birth date")
+                  .arg(Arg::with_name("date")
+                       .required(true)
+                       .help("Birth date as YYYY-MM-DD")
+                       )
+                  .arg(Arg::with_name("year")
+                       .long("year")
+                       .takes_value(true)
+                       .help("Year to find the card for. Defaults to the current year")
+                       )
+                  )
+             .subcommand(SubCommand::with_name("quintessence")
+                  .about("Quintessence of the reading drawn with the seed, spread and shuffle options given before the subcommand")
+                  )
+             )
+        .subcommand(SubCommand::with_name("oracle")
+             .about("Ask a yes or no question. The cards drawn lean towards yes or no; reversals lean the other way")
+             .arg(Arg::with_name("question")
+                  .multiple(true)
+                  .required(true)
+                  .help("Question to ask")
+                  )
+             .arg(Arg::with_name("three")
+                  .long("three")
+                  .help("Draw three cards instead of one")
+                  )
+             .arg(Arg::with_name("save")
+                  .long("save")
+                  .help("Record the answer and question in the journal")
+                  )
+             )
+        .subcommand(SubCommand::with_name("audit")
+             .about("Shuffle the deck many times and check that no card or position is favoured, using the shuffle options and seed given before the subcommand")
+             .arg(Arg::with_name("runs")
+                  .long("runs")
+                  .takes_value(true)
+                  .default_value("10000")
+                  .help("Number of shuffles")
+                  )
+             .arg(Arg::with_name("positions")
+                  .long("positions")
+                  .takes_value(true)
+                  .default_value("3")
+                  .help("Number of cards dealt from each shuffle")
+                  )
+             .arg(Arg::with_name("table")
+                  .long("table")
+                  .help("Also list how often each card landed in each position")
+                  )
+             )
+        .subcommand(SubCommand::with_name("probability")
+             .about("Work out the chance of a card turning up in the spread, using the spread, reversal chance and shuffle options given before the subcommand")
+             .arg(Arg::with_name("card")
+                  .multiple(true)
+                  .required(true)
+                  .help("Card to ask about")
+                  )
+             .arg(Arg::with_name("position")
+                  .long("position")
+                  .takes_value(true)
+                  .conflicts_with("with")
+                  .help("Only count the card in this position, numbered from 1")
+                  )
+             .arg(Arg::with_name("with")
+                  .long("with")
+                  .takes_value(true)
+                  .help("Only count readings where this card appears as well")
+                  )
+             .arg(Arg::with_name("reversed")
+                  .long("reversed")
+                  .help("Only count the cards when they are drawn reversed")
+                  )
+             .arg(Arg::with_name("simulate")
+                  .long("simulate")
+                  .help("Check the exact answer by shuffling and dealing the spread many times")
+                  )
+             .arg(Arg::with_name("runs")
+                  .long("runs")
+                  .takes_value(true)
+                  .default_value("10000")
+                  .help("Number of shuffles to simulate with --simulate")
+                  )
+             )
         .get_matches();
 
+    match matches.subcommand() {
+        ("daily", Some(sub_matches)) => daily(sub_matches),
+        ("card", Some(sub_matches)) => card(sub_matches),
+        ("study", Some(sub_matches)) => study(&matches, sub_matches, now),
+        ("journey", Some(sub_matches)) => journey(sub_matches),
+        ("search", Some(sub_matches)) => search_decks(sub_matches),
+        ("decan", Some(sub_matches)) => decan(&matches, sub_matches, now),
+        ("numerology", Some(sub_matches)) => numerology(&matches, sub_matches, now),
+        ("oracle", Some(sub_matches)) => oracle(&matches, sub_matches, now),
+        ("audit", Some(sub_matches)) => audit_shuffle(&matches, sub_matches, now),
+        ("probability", Some(sub_matches)) => probability(&matches, sub_matches, now),
+        _ => reading(&matches, now),
+    }
+}
+
+fn calc_paths(arguments: &clap::ArgMatches, tar_element: ElementType) -> std::path::PathBuf {
+    let tar_arg = match tar_element {
+        ElementType::Spread => "spread_path",
+        ElementType::Deck => "deck_path",
+        ElementType::Combinations => "combinations_path",
+        ElementType::Journey => "journey_path",
+    };
+    match arguments.is_present(tar_arg) {
+        true => std::path::PathBuf::from(
+            shellexpand::tilde(arguments.value_of(tar_arg).unwrap()).to_string()
+        ),
+        false => {
+            write_default_files(tar_element, arguments.is_present("overwrite_default_files")).expect("couldn't write default files")
+        },
+    }
+}
+
+fn calc_hash<T: Hash>(t: &T) -> u64 {
+    let mut s = DefaultHasher::new();
+    t.hash(&mut s);
+    s.finish()
+}
+
+fn entropy_source(matches: &clap::ArgMatches) -> EntropySource {
+    matches.value_of("entropy").map_or(EntropySource::Seed, |e| e.parse().unwrap())
+}
+
+/// Hashes the seed value with the question, if any. Without `--seed` the value is
+/// the current time, or today's date when a question is asked so that asking the
+/// same question again on the same day gives the same reading. Other entropy
+/// sources replace or add to this, and `--replay` skips it for a recorded seed.
+fn calc_seed(matches: &clap::ArgMatches, question: Option<&str>, now: std::time::SystemTime) -> u64 {
     #[derive(Hash)]
     struct InputSeed {
         value: String, 
     }
 
+    if let Some(replay) = matches.value_of("replay") {
+        return replay.parse().expect("Replay seed must be a whole number");
+    }
+    let default_value = match question {
+        Some(_) => chrono::Local::now().format("%Y-%m-%d").to_string(),
+        None => now.duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs().to_string(),
+    };
     let seed = InputSeed {
-        value: matches.value_of("seed").unwrap_or(
-             &now.duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs().to_string()
-            ).to_string(),
+        value: matches.value_of("seed").unwrap_or(&default_value).to_string(),
+    };
+    let seed = match question {
+        Some(question) => calc_hash(&(seed, question.split_whitespace().collect::<Vec<&str>>().join(" ").to_lowercase())),
+        None => calc_hash(&seed),
+    };
+    match entropy_source(matches) {
+        EntropySource::Seed => seed,
+        EntropySource::Os => os_seed(),
+        EntropySource::Ritual => {
+            let stdin = std::io::stdin();
+            mix_timings(seed, &ritual_timings(stdin.lock(), std::io::stdout(), RITUAL_PRESSES))
+        },
+    }
+}
+
+/// Prints the seed unless it was given with `--seed` or `--replay`, so
+/// anything drawn from the time, the OS or a ritual can be repeated.
+fn print_replay(matches: &clap::ArgMatches, seed: u64) {
+    if !matches.is_present("seed") && !matches.is_present("replay") {
+        println!("Seed {} (repeat with --replay {})", seed, seed);
+    }
+}
+
+/// Prints the message and exits, for input the user can fix.
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
+
+fn parse_or_exit<T: std::str::FromStr>(value: &str, message: &str) -> T {
+    value.parse().unwrap_or_else(|_| exit_with(message))
+}
+
+fn shuffle_options(matches: &clap::ArgMatches, deck_size: usize) -> ShuffleOptions {
+    let shuffle = ShuffleOptions {
+        method: matches.value_of("shuffle").unwrap().parse().unwrap(),
+        passes: parse_or_exit(matches.value_of("passes").unwrap(), "Passes must be a whole number"),
+        cut: matches.value_of("cut").map(|c| parse_or_exit(c, "Cut must be a whole number")),
+        jumper_chance: matches.value_of("jumpers").map_or(0.0, |j| parse_or_exit(j, "Jumper chance must be a number")),
+        reversal_chance: matches.value_of("reversals").map_or(0.0, |r| parse_or_exit(r, "Reversal chance must be a number")),
+    };
+    if shuffle.passes == 0 {
+        exit_with("Passes must be at least 1");
+    }
+    if let Some(cut) = shuffle.cut {
+        if cut == 0 || cut >= deck_size {
+            exit_with(&format!("Cut must be between 1 and {} for a deck of {} cards", deck_size.saturating_sub(1), deck_size));
+        }
+    }
+    if !(0.0..=1.0).contains(&shuffle.jumper_chance) {
+        exit_with("Jumper chance must be between 0 and 1");
+    }
+    if !(0.0..=1.0).contains(&shuffle.reversal_chance) {
+        exit_with("Reversal chance must be between 0 and 1");
+    }
+    shuffle
+}
+
+fn parse_date(value: &str) -> chrono::NaiveDate {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").expect("Dates must be written as YYYY-MM-DD")
+}
+
+fn card(sub_matches: &clap::ArgMatches) {
+    let deck_path = calc_paths(sub_matches, ElementType::Deck);
+    let deck = Deck::new_from_path(deck_path.as_path());
+    if sub_matches.is_present("list") {
+        Pager::new().setup();
+        deck.print_list(&mut std::io::stdout());
+        return;
+    }
+    let name_matches = match sub_matches.subcommand() {
+        ("info", Some(info_matches)) => info_matches,
+        _ => sub_matches,
+    };
+    let name = name_matches.values_of("name").unwrap().collect::<Vec<&str>>().join(" ");
+    lookup_card(&deck, &name).print_info(&mut std::io::stdout());
+}
+
+/// The single card in the deck best matching `name`. When there is no
+/// such card the closest names are listed and the program exits.
+fn lookup_card<'d>(deck: &'d Deck, name: &str) -> &'d terminal_tarot::deck::Card {
+    match deck.lookup(name).as_slice() {
+        [card] => return card,
+        [] => eprintln!("No card like \"{}\" in {}, see `terminal_tarot card --list`", name, deck.name),
+        cards => {
+            eprintln!("\"{}\" could be any of:", name);
+            for card in cards {
+                eprintln!("  {}", card.name());
+            }
+        },
+    }
+    std::process::exit(1);
+}
+
+fn study(matches: &clap::ArgMatches, sub_matches: &clap::ArgMatches, now: std::time::SystemTime) {
+    let deck_path = calc_paths(sub_matches, ElementType::Deck);
+    let deck = Deck::new_from_path(deck_path.as_path());
+    let focus: Focus = sub_matches.value_of("focus").unwrap().parse().unwrap();
+    if let Err(message) = focus.check(&deck) {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+    let new_limit = sub_matches.value_of("new").unwrap().parse().expect("New card limit must be a whole number");
+    let state_path = data_file_path(STUDY_FILENAME).expect("couldn't find study progress location");
+    let mut state = StudyState::load(&state_path).expect("couldn't read study progress");
+    let today = chrono::Local::now().date_naive();
+    let seed = calc_seed(matches, None, now);
+
+    let due = state.due_cards(&deck, &focus, today, new_limit);
+    if due.is_empty() {
+        println!("Nothing to study today");
+        return;
+    }
+    let labels: Vec<&str> = Grade::ALL.iter().map(|g| g.label()).collect();
+    for (i, card) in due.iter().enumerate() {
+        let question = Question::new(card, seed.wrapping_add(i as u64));
+        println!("Card {} of {}", i + 1, due.len());
+        println!("{}", question.prompt);
+        println!("Press ENTER to see the answer");
+        let mut press = String::new();
+        std::io::stdin().read_line(&mut press).unwrap();
+        println!("{}", question.answer);
+        println!();
+
+        let selection = Select::new()
+            .with_prompt("How well did you know it? (Esc to stop)")
+            .items(&labels)
+            .default(2)
+            .interact_opt()
+            .expect("Error at menu select");
+        match selection {
+            Some(grade) => state.grade(&deck, card, Grade::ALL[grade], today),
+            None => break,
+        }
+        state.save(&state_path).expect("couldn't save study progress");
+        println!();
+    }
+    print_replay(matches, seed);
+}
+
+fn journey(sub_matches: &clap::ArgMatches) {
+    let deck_path = calc_paths(sub_matches, ElementType::Deck);
+    let deck = Deck::new_from_path(deck_path.as_path());
+    let journey_path = calc_paths(sub_matches, ElementType::Journey);
+    let journey = Journey::new_from_path(journey_path.as_path());
+    let missing = journey.missing_ranks(&deck);
+    if !missing.is_empty() {
+        let ranks = missing.iter().map(|rank| rank.to_string()).collect::<Vec<String>>();
+        eprintln!("{} needs major arcana cards {} that {} doesn't have", journey.name, ranks.join(", "), deck.name);
+        std::process::exit(1);
+    }
+    let progress_path = data_file_path(PROGRESS_FILENAME).expect("couldn't find journey progress location");
+    let mut progress = JourneyProgress::load(&progress_path).expect("couldn't read journey progress");
+    if sub_matches.is_present("restart") {
+        progress.set_completed(&journey, 0);
+    }
+
+    let start = progress.completed(&journey);
+    if start >= journey.len() {
+        println!("You have finished {}. Pass --restart to walk it again", journey.name);
+        return;
+    }
+    if start == 0 {
+        println!("{}", journey.name);
+        println!("{}", "=".repeat(journey.name.len()));
+        println!("{}", journey.intro());
+        println!();
+    }
+    for index in start..journey.len() {
+        journey.print_step(index, &deck, &mut std::io::stdout());
+        progress.set_completed(&journey, index + 1);
+        progress.save(&progress_path).expect("couldn't save journey progress");
+        println!();
+        if index + 1 == journey.len() {
+            println!("{}", journey.outro());
+            break;
+        }
+        println!("Press ENTER to continue, or type q and ENTER to stop here");
+        let mut press = String::new();
+        std::io::stdin().read_line(&mut press).unwrap();
+        if press.trim().eq_ignore_ascii_case("q") {
+            break;
+        }
+        println!();
+    }
+}
+
+fn search_decks(sub_matches: &clap::ArgMatches) {
+    let deck_path = calc_paths(sub_matches, ElementType::Deck);
+    let decks = Deck::all_from_path(deck_path.as_path());
+    if sub_matches.is_present("index") {
+        Pager::new().setup();
+        for (keyword, cards) in keyword_index(&decks) {
+            println!("{}: {}", keyword, cards.iter().map(|c| c.name()).collect::<Vec<&str>>().join(", "));
+        }
+        return;
+    }
+
+    let term = sub_matches.values_of("term").unwrap().collect::<Vec<&str>>().join(" ");
+    if sub_matches.is_present("keyword") {
+        let index = keyword_index(&decks);
+        match index.get(&term.to_lowercase()) {
+            Some(cards) => cards.iter().for_each(|c| println!("{}", c.name())),
+            None => println!("No card has the keyword \"{}\"", term),
+        }
+        return;
+    }
+
+    let hits = search(&decks, &term);
+    if hits.is_empty() {
+        println!("Nothing mentions \"{}\"", term);
+        return;
+    }
+    let markers = match atty::is(atty::Stream::Stdout) {
+        true => ("\x1b[1;4m", "\x1b[0m"),
+        false => ("*", "*"),
     };
+    print_hits(&hits, &term, markers, &mut std::io::stdout());
+}
 
-    fn calc_paths(arguments: &clap::ArgMatches, tar_element: ElementType) -> std::path::PathBuf {
-        let tar_arg = match tar_element {
-            ElementType::Spread => "spread_path",
-            ElementType::Deck => "deck_path",
-        };
-        match arguments.is_present(tar_arg) {
-            true => std::path::PathBuf::from(
-                shellexpand::tilde(arguments.value_of(tar_arg).unwrap()).to_string()
-            ),
-            false => {
-                write_default_files(tar_element, arguments.is_present("overwrite_default_files")).expect("couldn't write default files")
-            },
+fn decan(matches: &clap::ArgMatches, sub_matches: &clap::ArgMatches, now: std::time::SystemTime) {
+    use chrono::Datelike;
+
+    let deck_path = calc_paths(sub_matches, ElementType::Deck);
+    let deck = Deck::new_from_path(deck_path.as_path());
+    let today = chrono::Local::now().date_naive();
+
+    if sub_matches.is_present("calendar") {
+        let year = sub_matches.value_of("year")
+            .map_or(today.year(), |y| y.parse().expect("Year must be a whole number"));
+        let entries = decan_calendar(&deck, year);
+        match sub_matches.is_present("ics") {
+            true => write_ics(&entries, chrono::Utc::now(), &mut std::io::stdout()),
+            false => print_calendar(&entries, &mut std::io::stdout()),
         }
+        return;
     }
 
-    let spread_path = calc_paths(&matches, ElementType::Spread);
-    let deck_path = calc_paths(&matches, ElementType::Deck);
+    let date = sub_matches.value_of("date").map_or(today, parse_date);
+    let entry = ruling_card(&deck, date).unwrap_or_else(|| panic!("{} has no card with a decan on {}", deck.name, date));
+    let title = format!("Decan for {}", date.format("%Y-%m-%d"));
+    println!("{}", title);
+    println!("{}", "=".repeat(title.len()));
+    println!("{}, {} to {}", entry.ruler(), entry.start.format("%m-%d"), entry.end.format("%m-%d"));
+    println!();
+    let seed = calc_seed(matches, None, now);
+    entry.card.print(seed, &mut std::io::stdout());
+    print_replay(matches, seed);
+}
+
+fn numerology(matches: &clap::ArgMatches, sub_matches: &clap::ArgMatches, now: std::time::SystemTime) {
+    use chrono::Datelike;
+
+    let seed = calc_seed(matches, matches.value_of("question"), now);
+    let deck_path = calc_paths(sub_matches, ElementType::Deck);
+    let mut deck = Deck::new_from_path(deck_path.as_path());
+    let (title, ranks) = match sub_matches.subcommand() {
+        ("birth", Some(birth_matches)) => {
+            let date = birth_matches.value_of("date").unwrap();
+            (format!("Birth Cards for {}", date), birth_cards(parse_date(date)))
+        },
+        ("year", Some(year_matches)) => {
+            let year = year_matches.value_of("year")
+                .map_or(chrono::Local::now().year(), |y| y.parse().expect("Year must be a whole number"));
+            let birth = parse_date(year_matches.value_of("date").unwrap());
+            (format!("Year Card for {}", year), vec![ year_card(birth, year) ])
+        },
+        _ => {
+            let spread_path = calc_paths(matches, ElementType::Spread);
+            let spread = Spread::new_from_path(spread_path.as_path());
+            let shuffle = shuffle_options(matches, deck.cards().len());
+            let filled_spread = FilledSpread::new_with_shuffle(spread, &mut deck, seed, &shuffle);
+            let rank = filled_spread.quintessence();
+            (String::from("Quintessence"), vec![ rank ])
+        },
+    };
 
-    fn calc_hash<T: Hash>(t: &T) -> u64 {
-        let mut s = DefaultHasher::new();
-        t.hash(&mut s);
-        s.finish()
+    println!("{}", title);
+    println!("{}", "=".repeat(title.len()));
+    println!();
+    for rank in ranks {
+        let card = deck.major(rank).unwrap_or_else(|| panic!("Deck has no major arcana card numbered {}", rank));
+        card.print(seed, &mut std::io::stdout());
+        println!();
     }
+    print_replay(matches, seed);
+}
 
+fn oracle(matches: &clap::ArgMatches, sub_matches: &clap::ArgMatches, now: std::time::SystemTime) {
+    let question = sub_matches.values_of("question").unwrap().collect::<Vec<&str>>().join(" ");
+    let seed = calc_seed(matches, Some(&question), now);
+    let deck_path = calc_paths(sub_matches, ElementType::Deck);
     let mut deck = Deck::new_from_path(deck_path.as_path());
+    let deck_name = deck.name.clone();
+    let count = match sub_matches.is_present("three") {
+        true => 3,
+        false => 1,
+    };
+    let shuffle = shuffle_options(matches, deck.cards().len());
+    let reading = OracleReading::new(&question, &mut deck, count, seed, &shuffle);
+
+    if sub_matches.is_present("save") {
+        let journal_path = data_file_path(JOURNAL_FILENAME).expect("couldn't find journal location");
+        let record = reading.to_record(
+            &deck_name,
+            seed,
+            now.duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs()
+            );
+        append_reading(&journal_path, &record).expect("couldn't write to journal");
+    }
+    reading.print(seed, &mut std::io::stdout());
+    print_replay(matches, seed);
+}
+
+fn audit_shuffle(matches: &clap::ArgMatches, sub_matches: &clap::ArgMatches, now: std::time::SystemTime) {
+    let deck_path = calc_paths(sub_matches, ElementType::Deck);
+    let deck = Deck::new_from_path(deck_path.as_path());
+    let runs = parse_or_exit(sub_matches.value_of("runs").unwrap(), "Runs must be a whole number");
+    let positions = parse_or_exit(sub_matches.value_of("positions").unwrap(), "Positions must be a whole number");
+    if positions > deck.cards().len() {
+        exit_with(&format!("Can't deal {} positions from a deck of {} cards", positions, deck.cards().len()));
+    }
+    if runs == 0 {
+        exit_with("Runs must be at least 1");
+    }
+    if deck.cards().len() < 2 {
+        exit_with("A deck needs at least 2 cards to be audited");
+    }
+    let seed = calc_seed(matches, None, now);
+    let report = audit(&deck, runs, positions, seed, &shuffle_options(matches, deck.cards().len()));
+    report.print(&mut std::io::stdout());
+    if sub_matches.is_present("table") {
+        println!();
+        report.print_table(&mut std::io::stdout());
+    }
+    print_replay(matches, seed);
+}
+
+
+fn probability(matches: &clap::ArgMatches, sub_matches: &clap::ArgMatches, now: std::time::SystemTime) {
+    let deck_path = calc_paths(sub_matches, ElementType::Deck);
+    let deck = Deck::new_from_path(deck_path.as_path());
+    let spread_path = calc_paths(matches, ElementType::Spread);
     let spread = Spread::new_from_path(spread_path.as_path());
-    let filled_spread = FilledSpread::new(spread, &mut deck, calc_hash(&seed));
+    let positions = spread.position_names();
+    let shuffle = shuffle_options(matches, deck.cards().len());
+
+    let name = sub_matches.values_of("card").unwrap().collect::<Vec<&str>>().join(" ");
+    let event = match (sub_matches.value_of("position"), sub_matches.value_of("with")) {
+        (Some(position), _) => {
+            let position: usize = parse_or_exit(position, "Position must be a whole number");
+            if position == 0 || position > positions.len() {
+                exit_with(&format!("{} has positions 1 to {}", spread, positions.len()));
+            }
+            Event::InPosition(position - 1)
+        },
+        (None, Some(other)) => Event::With(lookup_card(&deck, other).name().to_string()),
+        (None, None) => Event::Appears,
+    };
+    let query = Query {
+        card: lookup_card(&deck, &name).name().to_string(),
+        event,
+        reversed: sub_matches.is_present("reversed"),
+    };
+    if query.event == Event::With(query.card.clone()) {
+        exit_with("Pick two different cards");
+    }
+    let runs = match sub_matches.is_present("simulate") {
+        true => Some(parse_or_exit(sub_matches.value_of("runs").unwrap(), "Runs must be a whole number")),
+        false => None,
+    };
+    if runs == Some(0) {
+        exit_with("Runs must be at least 1");
+    }
+
+    let title = format!("Chance that {}", query.describe(&positions));
+    println!("{}", title);
+    println!("{}", "=".repeat(title.len()));
+    println!("{} with {} positions, {} cards", spread, positions.len(), deck.cards().len());
+    println!();
+    println!(
+        "Exact:       {} with a uniform shuffle",
+        describe_chance(exact(deck.cards().len(), positions.len(), shuffle.reversal_chance, &query))
+        );
+    if shuffle.method != ShuffleMethod::Random {
+        println!("             {} shuffles don't mix the deck evenly, so only --simulate reflects them",
+            matches.value_of("shuffle").unwrap());
+    }
+    if let Some(runs) = runs {
+        let seed = calc_seed(matches, None, now);
+        let simulated = simulate(&deck, positions.len(), &query, runs, seed, &shuffle);
+        println!("Monte Carlo: {} over {} shuffles", describe_chance(simulated), runs);
+        print_replay(matches, seed);
+    }
+}
+
+fn daily(sub_matches: &clap::ArgMatches) {
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let key = sub_matches.value_of("key").unwrap_or("");
+    let deck_arg = sub_matches.value_of("deck_path").unwrap_or("");
+    let cache_path = data_file_path(DAILY_FILENAME).expect("couldn't find daily card location");
+
+    let deck_path = calc_paths(sub_matches, ElementType::Deck);
+    let fingerprint = deck_fingerprint(&deck_path);
+
+    let daily_card = match DailyCard::load(&cache_path, &date, key, deck_arg, fingerprint) {
+        Some(cached) => cached,
+        None => {
+            let mut deck = Deck::new_from_path(deck_path.as_path());
+            let drawn = DailyCard::draw(&mut deck, deck_arg, fingerprint, &date, key);
+            drawn.save(&cache_path).expect("couldn't cache daily card");
+            drawn
+        },
+    };
+    match sub_matches.is_present("oneline") {
+        true => println!("{}", daily_card.oneline),
+        false => print!("{}", daily_card.text),
+    }
+}
+
+fn reading(matches: &clap::ArgMatches, now: std::time::SystemTime) {
+    let seed = calc_seed(matches, matches.value_of("question"), now);
+
+    let spread_path = calc_paths(matches, ElementType::Spread);
+    let deck_path = calc_paths(matches, ElementType::Deck);
+    let combinations_path = calc_paths(matches, ElementType::Combinations);
+
+    let mut deck = Deck::new_from_path(deck_path.as_path());
+    let deck_name = deck.name.clone();
+    deck.add_combinations(load_combinations(combinations_path.as_path(), &deck_name));
+    let shuffle = shuffle_options(matches, deck.cards().len());
+    if matches.is_present("oneline") {
+        println!("{}", deck.deal_one(seed, &shuffle).oneline(seed));
+        return;
+    }
+    let spread = Spread::new_from_path(spread_path.as_path());
+    let filled_spread = FilledSpread::new_with_shuffle(spread, &mut deck, seed, &shuffle);
+
+    let journal_path = data_file_path(JOURNAL_FILENAME).expect("couldn't find journal location");
+    let meaning_strategy = match matches.is_present("detailed") {
+        true => Some(MeaningStrategy::All),
+        false => matches.value_of("meanings").map(|m| m.parse().unwrap()),
+    };
+    let mut options = ReadingOptions {
+        shadow_card: matches.is_present("shadow_card"),
+        remaining_summary: matches.is_present("remaining_summary"),
+        summary: matches.is_present("summary"),
+        dignities: matches.is_present("dignities"),
+        narrative: matches.is_present("narrative"),
+        correspondences: matches.is_present("correspondences"),
+        timing: matches.is_present("timing"),
+        question: matches.value_of("question").map(String::from),
+        template: matches.value_of("template").map(|t| std::path::PathBuf::from(shellexpand::tilde(t).to_string())),
+        meaning_strategy,
+        meaning_history: MeaningHistory::default(),
+    };
+    if filled_spread.meaning_strategy(&options) == MeaningStrategy::Rotating {
+        options.meaning_history = MeaningHistory::from_records(
+            &load_readings(&journal_path).expect("couldn't read journal")
+            );
+    }
+
+    if matches.is_present("save") {
+        let record = filled_spread.to_record(
+            &deck_name,
+            seed,
+            now.duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs(),
+            &options
+            );
+        append_reading(&journal_path, &record).expect("couldn't write to journal");
+    }
 
     if !matches.is_present("interactive") {
       Pager::new().setup();
     }
 
-    filled_spread.print(
+    filled_spread.print_with(
         matches.is_present("interactive"),
-        calc_hash(&seed), 
+        seed, 
+        &options,
         &mut std::io::stdout()
         );
+    print_replay(matches, seed);
 }
@@ -0,0 +1,170 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+
+use crate::deck::{Card, Decan, Deck};
+
+/// A card's decan as it falls in a particular year.
+#[derive(PartialEq, Debug)]
+pub struct DecanEntry<'a> {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub card: &'a Card,
+}
+
+impl DecanEntry<'_> {
+    /// The decan's planet and sign, e.g. "Mars in Aries".
+    pub fn ruler(&self) -> String {
+        let correspondences = self.card.correspondences();
+        match (&correspondences.planet, &correspondences.zodiac) {
+            (Some(planet), Some(zodiac)) => format!("{} in {}", planet, zodiac),
+            (None, Some(zodiac)) => zodiac.clone(),
+            (Some(planet), None) => planet.clone(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+fn decans(deck: &Deck) -> impl Iterator<Item = (&Card, &Decan)> {
+    deck.cards().iter().filter_map(|card| card.correspondences().decan.as_ref().map(|decan| (card, decan)))
+}
+
+/// The card whose decan rules the given date, if the deck has decan dates.
+pub fn ruling_card(deck: &Deck, date: NaiveDate) -> Option<DecanEntry<'_>> {
+    decans(deck).find(|(_, decan)| decan.contains(date)).map(|(card, decan)| {
+        let (start, end) = match decan.span(date.year()) {
+            (start, end) if start <= date => (start, end),
+            _ => decan.span(date.year() - 1),
+        };
+        DecanEntry { start, end, card }
+    })
+}
+
+/// Every decan touching the given year, in date order. Decans running over
+/// the new year appear at both ends.
+pub fn decan_calendar(deck: &Deck, year: i32) -> Vec<DecanEntry<'_>> {
+    let mut entries: Vec<DecanEntry> = decans(deck)
+        .flat_map(|(card, decan)| vec![ decan.span(year - 1), decan.span(year) ]
+            .into_iter()
+            .map(move |(start, end)| DecanEntry { start, end, card }))
+        .filter(|entry| entry.start.year() == year || entry.end.year() == year)
+        .collect();
+    entries.sort_by_key(|entry| entry.start);
+    entries
+}
+
+pub fn print_calendar(entries: &[DecanEntry], mut writer: impl std::io::Write) {
+    for entry in entries {
+        writeln!(
+            writer,
+            "{} to {}  {} ({})",
+            entry.start.format("%Y-%m-%d"),
+            entry.end.format("%Y-%m-%d"),
+            entry.card.name(),
+            entry.ruler()
+            ).unwrap();
+    }
+}
+
+/// Escapes the characters iCalendar gives a meaning to in text values.
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,")
+}
+
+/// Writes the entries as an iCalendar file of all-day events, stamped with
+/// the time the file was generated.
+pub fn write_ics(entries: &[DecanEntry], generated: DateTime<Utc>, mut writer: impl std::io::Write) {
+    write!(writer, "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//terminal_tarot//Decan Calendar//EN\r\n").unwrap();
+    let stamp = generated.format("%Y%m%dT%H%M%SZ");
+    for entry in entries {
+        let start = entry.start.format("%Y%m%d");
+        write!(
+            writer,
+            "BEGIN:VEVENT\r\nUID:{}-{}@terminal_tarot\r\nDTSTAMP:{}\r\nDTSTART;VALUE=DATE:{}\r\nDTEND;VALUE=DATE:{}\r\nSUMMARY:{}\r\nEND:VEVENT\r\n",
+            start,
+            escape_text(&entry.card.name().replace(' ', "_")),
+            stamp,
+            start,
+            (entry.end + Duration::days(1)).format("%Y%m%d"),
+            escape_text(&format!("{} ({})", entry.card.name(), entry.ruler()))
+            ).unwrap();
+    }
+    write!(writer, "END:VCALENDAR\r\n").unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use crate::calendar::{decan_calendar, escape_text, print_calendar, ruling_card, write_ics};
+    use crate::deck::Deck;
+    use crate::stored_element::StoredElement;
+
+    fn decan_deck() -> Deck {
+        Deck::new_from_json(r#"{
+            "name": "decan deck",
+            "cards": [
+                {
+                    "rank": 2, "suit": "wands", "name": "two of wands",
+                    "meanings": { "light": [], "shadow": [] }, "keywords": [], "fortune_telling": [],
+                    "correspondences": { "planet": "Mars", "zodiac": "Aries", "decan": { "start": "03-21", "end": "03-30" } }
+                },
+                {
+                    "rank": 3, "suit": "coins", "name": "three of coins",
+                    "meanings": { "light": [], "shadow": [] }, "keywords": [], "fortune_telling": [],
+                    "correspondences": { "planet": "Mars", "zodiac": "Capricorn", "decan": { "start": "12-31", "end": "01-09" } }
+                },
+                {
+                    "rank": 8, "suit": "cups", "name": "eight of cups",
+                    "meanings": { "light": [], "shadow": [] }, "keywords": [], "fortune_telling": [],
+                    "correspondences": { "planet": "Saturn", "zodiac": "Pisces", "decan": { "start": "02-19", "end": "02-29" } }
+                },
+                {
+                    "rank": 1, "suit": "cups", "name": "ace of cups",
+                    "meanings": { "light": [], "shadow": [] }, "keywords": [], "fortune_telling": []
+                }
+            ]
+        }"#)
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn decan_ruling_card() {
+        let deck = decan_deck();
+        let entry = ruling_card(&deck, date(2023, 3, 25)).unwrap();
+        assert_eq!(entry.card.name(), "two of wands");
+        assert_eq!(entry.ruler(), "Mars in Aries");
+
+        let entry = ruling_card(&deck, date(2024, 1, 5)).unwrap();
+        assert_eq!(entry.card.name(), "three of coins");
+        assert_eq!((entry.start, entry.end), (date(2023, 12, 31), date(2024, 1, 9)));
+
+        assert_eq!(ruling_card(&deck, date(2024, 2, 29)).unwrap().end, date(2024, 2, 29));
+        assert_eq!(ruling_card(&deck, date(2023, 2, 28)).unwrap().end, date(2023, 2, 28));
+        assert_eq!(ruling_card(&deck, date(2023, 7, 1)), None);
+    }
+
+    #[test]
+    fn decan_calendar_export() {
+        let deck = decan_deck();
+        let entries = decan_calendar(&deck, 2023);
+        let mut test_result = Vec::new();
+        print_calendar(&entries, &mut test_result);
+        assert_eq!(String::from_utf8(test_result).unwrap(), r#"2022-12-31 to 2023-01-09  three of coins (Mars in Capricorn)
+2023-02-19 to 2023-02-28  eight of cups (Saturn in Pisces)
+2023-03-21 to 2023-03-30  two of wands (Mars in Aries)
+2023-12-31 to 2024-01-09  three of coins (Mars in Capricorn)
+"#);
+
+        let generated = Utc.with_ymd_and_hms(2022, 11, 5, 14, 30, 9).unwrap();
+        let mut test_result = Vec::new();
+        write_ics(&entries[1..2], generated, &mut test_result);
+        assert_eq!(String::from_utf8(test_result).unwrap(), "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+            PRODID:-//terminal_tarot//Decan Calendar//EN\r\nBEGIN:VEVENT\r\n\
+            UID:20230219-eight_of_cups@terminal_tarot\r\nDTSTAMP:20221105T143009Z\r\n\
+            DTSTART;VALUE=DATE:20230219\r\nDTEND;VALUE=DATE:20230301\r\n\
+            SUMMARY:eight of cups (Saturn in Pisces)\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n");
+
+        assert_eq!(escape_text(r"Mars; Sol, and \ more"), r"Mars\; Sol\, and \\ more");
+    }
+}
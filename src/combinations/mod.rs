@@ -0,0 +1,125 @@
+use serde::{Deserialize, Deserializer};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::stored_element::StoredElement;
+use crate::template::CombinationModel;
+
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Combination {
+    #[serde(deserialize_with = "at_least_two_cards")]
+    cards: Vec<String>,
+    meaning: String,
+    #[serde(default)]
+    adjacent: bool,
+}
+
+fn at_least_two_cards<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>
+{
+    use serde::de::Error;
+
+    let cards = Vec::<String>::deserialize(deserializer)?;
+    match cards.len() {
+        0 | 1 => Err(D::Error::invalid_length(cards.len(), &"at least two cards")),
+        _ => Ok(cards),
+    }
+}
+
+impl Combination {
+    pub fn cards(&self) -> &[String] {
+        &self.cards
+    }
+
+    pub fn meaning(&self) -> &str {
+        &self.meaning
+    }
+
+    /// Whether the cards only combine when they sit in neighbouring positions.
+    pub fn adjacent(&self) -> bool {
+        self.adjacent
+    }
+
+    pub fn to_model(&self) -> CombinationModel {
+        CombinationModel {
+            cards: self.cards.clone(),
+            meaning: self.meaning.clone(),
+            adjacent: self.adjacent,
+        }
+    }
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+pub struct CombinationFile {
+    name: String,
+    deck: String,
+    combinations: Vec<Combination>,
+}
+
+impl StoredElement for CombinationFile {
+    fn new_from_json(json: &str) -> CombinationFile {
+        serde_json::from_str(json).expect("Failed to deserialize combinations file")
+    }
+}
+
+/// Collects the combinations from every file under `path` written for `deck_name`.
+pub fn load_combinations(path: &Path, deck_name: &str) -> Vec<Combination> {
+    let mut found = Vec::new();
+    for e in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+        if e.metadata().unwrap().is_file() {
+            let file = CombinationFile::new_from_file(e.path());
+            if file.deck == deck_name {
+                found.extend(file.combinations);
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::combinations::{load_combinations, Combination, CombinationFile};
+    use crate::stored_element::StoredElement;
+
+    #[test]
+    fn bundled_combinations_deserial() {
+        let file = CombinationFile::new_from_json(
+            include_str!("../default_files/included_combinations/standard_combinations.json")
+            );
+        assert_eq!(file.deck, "Mark McElroy's Guide to Tarot Meanings");
+        assert!(file.combinations.iter().any(|combo| combo.adjacent()));
+    }
+
+    #[test]
+    fn combinations_need_two_cards() {
+        let parse = |cards: &str| serde_json::from_str::<Combination>(
+            &format!(r#"{{ "cards": {}, "meaning": "m", "adjacent": true }}"#, cards)
+            );
+        assert!(parse(r#"["test_name", "test_name1"]"#).is_ok());
+        let error = parse(r#"["test_name"]"#).unwrap_err().to_string();
+        assert!(error.contains("expected at least two cards"), "{}", error);
+        assert!(parse("[]").is_err());
+    }
+
+    #[test]
+    fn combinations_load_for_deck() {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("terminal_tarot_combinations_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.json"), r#"{
+            "name": "a", "deck": "test deck",
+            "combinations": [ { "cards": ["test_name", "test_name1"], "meaning": "pair" } ]
+        }"#).unwrap();
+        std::fs::write(dir.join("b.json"), r#"{
+            "name": "b", "deck": "other deck",
+            "combinations": [ { "cards": ["x", "y"], "meaning": "other" } ]
+        }"#).unwrap();
+
+        let loaded = load_combinations(&dir, "test deck");
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].meaning(), "pair");
+        assert!(!loaded[0].adjacent());
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::deck::Deck;
+
+pub const DAILY_FILENAME: &str = "daily_card.json";
+
+/// The card drawn for a day, cached so repeat invocations skip loading the deck.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct DailyCard {
+    pub date: String,
+    pub seed: u64,
+    pub deck: String,
+    /// Hash of the deck files' contents, so edits to the deck are noticed.
+    #[serde(default)]
+    pub fingerprint: u64,
+    pub card: String,
+    pub text: String,
+    pub oneline: String,
+}
+
+/// Seed shared by every draw on `date` for the given user key.
+pub fn daily_seed(date: &str, key: &str) -> u64 {
+    let mut s = DefaultHasher::new();
+    (date, key).hash(&mut s);
+    s.finish()
+}
+
+/// Hash of every file under `path`, cheap next to parsing the deck.
+pub fn deck_fingerprint(path: &Path) -> u64 {
+    let mut s = DefaultHasher::new();
+    let files = WalkDir::new(path).sort_by(|a, b| a.file_name().cmp(b.file_name()));
+    for e in files.into_iter().filter_map(|e| e.ok()) {
+        if e.metadata().is_ok_and(|m| m.is_file()) {
+            e.path().hash(&mut s);
+            std::fs::read(e.path()).unwrap_or_default().hash(&mut s);
+        }
+    }
+    s.finish()
+}
+
+impl DailyCard {
+    pub fn draw(deck: &mut Deck, deck_path: &str, fingerprint: u64, date: &str, key: &str) -> DailyCard {
+        let seed = daily_seed(date, key);
+        deck.shuffle_deck(seed);
+        let card = &deck.draw(1)[0];
+        let title = format!("Card of the Day, {}", date);
+        let mut text = format!("{}\n{}\n", title, "-".repeat(title.len()));
+        let mut body = Vec::new();
+        card.print(seed, &mut body);
+        text.push_str(&String::from_utf8(body).unwrap());
+        DailyCard {
+            date: date.to_string(),
+            seed,
+            deck: deck_path.to_string(),
+            fingerprint,
+            card: card.name().to_string(),
+            text,
+            oneline: card.oneline(seed),
+        }
+    }
+
+    /// Returns the cached card if it was drawn for this date, key and deck,
+    /// and the deck hasn't changed since.
+    pub fn load(path: &Path, date: &str, key: &str, deck_path: &str, fingerprint: u64) -> Option<DailyCard> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        let cached: DailyCard = serde_json::from_str(&contents).ok()?;
+        let same_deck = cached.deck == deck_path && cached.fingerprint == fingerprint;
+        match cached.date == date && cached.seed == daily_seed(date, key) && same_deck {
+            true => Some(cached),
+            false => None,
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::daily::{daily_seed, deck_fingerprint, DailyCard};
+    use crate::deck::Deck;
+    use crate::deck::test_utils::utils::return_test_deck;
+    use crate::stored_element::StoredElement;
+
+    #[test]
+    fn daily_card_stable_per_day() {
+        let mut deck = Deck::new_from_json(&return_test_deck());
+        let first = DailyCard::draw(&mut deck, "deck.json", 7, "2024-03-01", "me");
+        let mut deck = Deck::new_from_json(&return_test_deck());
+        let again = DailyCard::draw(&mut deck, "deck.json", 7, "2024-03-01", "me");
+        assert_eq!(first, again);
+        assert!(first.text.starts_with("Card of the Day, 2024-03-01\n"));
+        assert_ne!(daily_seed("2024-03-01", "me"), daily_seed("2024-03-02", "me"));
+        assert_ne!(daily_seed("2024-03-01", "me"), daily_seed("2024-03-01", "you"));
+    }
+
+    #[test]
+    fn daily_card_cache() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("terminal_tarot_daily_{}.json", std::process::id()));
+        let mut deck = Deck::new_from_json(&return_test_deck());
+        let card = DailyCard::draw(&mut deck, "deck.json", 7, "2024-03-01", "");
+        card.save(&path).unwrap();
+        assert_eq!(DailyCard::load(&path, "2024-03-01", "", "deck.json", 7), Some(card));
+        assert_eq!(DailyCard::load(&path, "2024-03-02", "", "deck.json", 7), None);
+        assert_eq!(DailyCard::load(&path, "2024-03-01", "other", "deck.json", 7), None);
+        assert_eq!(DailyCard::load(&path, "2024-03-01", "", "other.json", 7), None);
+        assert_eq!(DailyCard::load(&path, "2024-03-01", "", "deck.json", 8), None);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut deck_path = std::env::temp_dir();
+        deck_path.push(format!("terminal_tarot_daily_deck_{}.json", std::process::id()));
+        std::fs::write(&deck_path, return_test_deck()).unwrap();
+        let before = deck_fingerprint(&deck_path);
+        assert_eq!(deck_fingerprint(&deck_path), before);
+        std::fs::write(&deck_path, return_test_deck().replace("test_fortune1", "edited")).unwrap();
+        assert_ne!(deck_fingerprint(&deck_path), before);
+        std::fs::remove_file(&deck_path).unwrap();
+    }
+}
@@ -1,18 +1,153 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use chrono::{Datelike, NaiveDate};
 use crate::stored_element::StoredElement;
+use crate::summary::SummaryNotes;
+use crate::combinations::Combination;
+use crate::template::{CardModel, ReadingTemplate};
+use crate::journal::ReadingRecord;
 
-#[derive(Deserialize, PartialEq, Debug)]
+#[allow(dead_code)]
+#[derive(Deserialize, PartialEq, Debug, Clone)]
 struct CardGraphic {
     graph_string: String,
 }
 
-#[derive(Deserialize, PartialEq, Debug)]
+#[derive(Deserialize, PartialEq, Debug, Clone)]
 struct Meaning {
     light: Vec<String>,
     shadow: Vec<String>,
 }
 
-#[derive(Deserialize, PartialEq, Debug)]
+/// Span of the year ruled by a decan, as `MM-DD` dates.
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
+pub struct Decan {
+    pub start: String,
+    pub end: String,
+}
+
+impl Decan {
+    fn month_day(value: &str) -> (u32, u32) {
+        let mut parts = value.splitn(2, '-').map(|p| p.parse::<u32>().ok());
+        match (parts.next().flatten(), parts.next().flatten()) {
+            (Some(month), Some(day)) => (month, day),
+            _ => panic!("Decan dates must be written as MM-DD, found \"{}\"", value),
+        }
+    }
+
+    /// Whether the decan rules the given date, including decans that run over the new year.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        let day = (date.month(), date.day());
+        let start = Decan::month_day(&self.start);
+        let end = Decan::month_day(&self.end);
+        match start <= end {
+            true => start <= day && day <= end,
+            false => start <= day || day <= end,
+        }
+    }
+
+    /// First and last dates of the decan starting in `year`. A decan ending
+    /// on the 29th of February ends on the 28th outside leap years.
+    pub fn span(&self, year: i32) -> (NaiveDate, NaiveDate) {
+        fn date(year: i32, (month, day): (u32, u32)) -> NaiveDate {
+            NaiveDate::from_ymd_opt(year, month, day)
+                .or_else(|| NaiveDate::from_ymd_opt(year, month, day - 1))
+                .expect("Decan dates must be real days of the year")
+        }
+        let start = Decan::month_day(&self.start);
+        let end = Decan::month_day(&self.end);
+        let end_year = match start <= end {
+            true => year,
+            false => year + 1,
+        };
+        (date(year, start), date(end_year, end))
+    }
+}
+
+/// Astrological and Kabbalistic attributions of a card. Decks may leave any of them out.
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Default)]
+pub struct Correspondences {
+    pub element: Option<String>,
+    pub planet: Option<String>,
+    pub zodiac: Option<String>,
+    pub decan: Option<Decan>,
+    pub hebrew_letter: Option<String>,
+    pub tree_of_life_path: Option<u8>,
+}
+
+/// The kind of question a spread position asks, used to pick position-specific meanings.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum PositionCategory {
+    Past,
+    Present,
+    Future,
+    Obstacle,
+    Advice,
+    Outcome,
+}
+
+/// How a card's fortune, light and shadow meanings are picked from their lists.
+#[derive(Deserialize, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum MeaningStrategy {
+    Random,
+    First,
+    Rotating,
+    Weighted,
+    All,
+}
+
+impl std::str::FromStr for MeaningStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<MeaningStrategy, String> {
+        match s.to_lowercase().as_str() {
+            "random" => Ok(MeaningStrategy::Random),
+            "first" => Ok(MeaningStrategy::First),
+            "rotating" => Ok(MeaningStrategy::Rotating),
+            "weighted" => Ok(MeaningStrategy::Weighted),
+            "all" => Ok(MeaningStrategy::All),
+            _ => Err(format!("Unknown meaning strategy: {}", s)),
+        }
+    }
+}
+
+/// When each meaning was last shown, by position in the journal, for the rotating strategy.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct MeaningHistory {
+    last_shown: HashMap<String, usize>,
+}
+
+impl MeaningHistory {
+    pub fn from_records(records: &[ReadingRecord]) -> MeaningHistory {
+        let mut last_shown = HashMap::new();
+        for (index, record) in records.iter().enumerate() {
+            for position in record.positions.iter() {
+                for meaning in position.shown_meanings.iter() {
+                    last_shown.insert(meaning.clone(), index);
+                }
+            }
+        }
+        MeaningHistory { last_shown }
+    }
+
+    fn last_shown(&self, meaning: &str) -> Option<usize> {
+        self.last_shown.get(meaning).copied()
+    }
+}
+
+#[derive(Deserialize, PartialEq, Debug, Clone, Default)]
+struct CategoryMeaning {
+    #[serde(default)]
+    fortune_telling: Vec<String>,
+    #[serde(default)]
+    light: Vec<String>,
+    #[serde(default)]
+    shadow: Vec<String>,
+}
+
+#[derive(Deserialize, PartialEq, Debug, Clone)]
 pub struct Card {
     rank: u8,
     suit: String,
@@ -20,27 +155,264 @@ pub struct Card {
     meanings: Meaning,
     keywords: Vec<String>,
     fortune_telling: Vec<String>,
+    #[serde(default)]
+    category_meanings: HashMap<PositionCategory, CategoryMeaning>,
+    #[serde(default)]
+    weights: HashMap<String, u32>,
+    #[serde(default)]
+    correspondences: Correspondences,
+    #[serde(default)]
+    polarity: Option<i32>,
+    #[serde(skip)]
+    reversed: bool,
 }
 
+pub const MAJOR_SUIT: &str = "major";
+const CARD_BACK: char = '\u{1F0A0}';
+const ONELINE_WIDTH: usize = 80;
+
 impl Card {
-    pub fn print(&self, seed: u64, mut writer: impl std::io::Write) {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn suit(&self) -> &str {
+        &self.suit
+    }
+
+    pub fn rank(&self) -> u8 {
+        self.rank
+    }
+
+    pub fn is_major(&self) -> bool {
+        self.suit == MAJOR_SUIT
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    pub fn keywords(&self) -> &[String] {
+        &self.keywords
+    }
+
+    pub fn fortunes(&self) -> &[String] {
+        &self.fortune_telling
+    }
+
+    pub fn lights(&self) -> &[String] {
+        &self.meanings.light
+    }
+
+    pub fn shadows(&self) -> &[String] {
+        &self.meanings.shadow
+    }
+
+    pub fn correspondences(&self) -> &Correspondences {
+        &self.correspondences
+    }
+
+    /// How strongly the card leans towards yes (positive) or no (negative).
+    /// Without a value in the deck file, swords and fives lean no, other minor
+    /// cards lean yes and the major arcana are undecided. Reversal flips it.
+    pub fn polarity(&self) -> i32 {
+        let upright = self.polarity.unwrap_or(match (self.suit.as_str(), self.rank) {
+            (MAJOR_SUIT, _) => 0,
+            ("swords", _) | (_, 5) => -1,
+            _ => 1,
+        });
+        match self.reversed {
+            true => -upright,
+            false => upright,
+        }
+    }
+
+    /// Card name, marked when the card was drawn reversed.
+    pub fn title(&self) -> String {
+        match self.reversed {
+            true => format!("{} (Reversed)", self.name),
+            false => self.name.clone(),
+        }
+    }
+
+    pub fn keyword(&self, seed: u64) -> &str {
         use rand::SeedableRng;
         use rand::rngs;
         use rand::seq::SliceRandom;
 
         let mut rng = rngs::StdRng::seed_from_u64(seed);
-        writeln!(writer, "{}", self.name).unwrap();
-        writeln!(writer, "{}", std::iter::repeat("-").take(self.name.len()).collect::<String>()).unwrap();
-        writeln!(writer, "{}", self.fortune_telling.choose(&mut rng).unwrap_or(&String::from("No Fortune"))).unwrap();
-        writeln!(writer, "Light: {}", self.meanings.light.choose(&mut rng).unwrap_or(&String::from("No Light meaning"))).unwrap();
-        writeln!(writer, "Shadow: {}", self.meanings.shadow.choose(&mut rng).unwrap_or(&String::from("No Shadow meaning"))).unwrap();
+        self.keywords.choose(&mut rng).map_or("No Keyword", |k| k.as_str())
     }
+
+    /// Unicode playing card for this card: trumps for the major arcana, and
+    /// spades, hearts, diamonds and clubs for swords, cups, coins and wands.
+    pub fn glyph(&self) -> char {
+        let base = match (self.suit.as_str(), self.rank) {
+            (MAJOR_SUIT, 0..=21) => 0x1F0E0,
+            ("swords", 1..=14) => 0x1F0A0,
+            ("cups", 1..=14) => 0x1F0B0,
+            ("coins", 1..=14) | ("pentacles", 1..=14) => 0x1F0C0,
+            ("wands", 1..=14) => 0x1F0D0,
+            _ => return CARD_BACK,
+        };
+        std::char::from_u32(base + self.rank as u32).unwrap_or(CARD_BACK)
+    }
+
+    /// Glyph, name, keyword and fortune on a single line, with the fortune
+    /// shortened so the line fits a terminal.
+    pub fn oneline(&self, seed: u64) -> String {
+        let model = self.to_model(seed, None, MeaningStrategy::Random, &MeaningHistory::default());
+        let lead = format!("{} {} ({}): ", self.glyph(), model.title, model.keyword);
+        let room = ONELINE_WIDTH.saturating_sub(lead.chars().count());
+        let fortune = match model.fortune.chars().count() > room {
+            true => {
+                let cut: String = model.fortune.chars().take(room.saturating_sub(1)).collect();
+                let cut = match cut.rfind(' ') {
+                    Some(space) => cut[..space].to_string(),
+                    None => cut,
+                };
+                format!("{}\u{2026}", cut.trim_end_matches(|c: char| c.is_ascii_punctuation() || c == ' '))
+            },
+            false => model.fortune,
+        };
+        format!("{}{}", lead, fortune)
+    }
+
+    /// The card with its fortune and meanings chosen for this seed, preferring
+    /// meanings written for the position's category when the deck has them.
+    pub fn to_model(
+        &self,
+        seed: u64,
+        category: Option<PositionCategory>,
+        strategy: MeaningStrategy,
+        history: &MeaningHistory
+        ) -> CardModel {
+        use rand::SeedableRng;
+        use rand::rngs;
+
+        fn pool<'c>(specific: Option<&'c Vec<String>>, general: &'c [String]) -> &'c [String] {
+            match specific {
+                Some(specific) if !specific.is_empty() => specific,
+                _ => general,
+            }
+        }
+
+        let specific = category.and_then(|c| self.category_meanings.get(&c));
+        let mut rng = rngs::StdRng::seed_from_u64(seed);
+        let fortunes = self.select(pool(specific.map(|m| &m.fortune_telling), &self.fortune_telling), strategy, history, &mut rng, "No Fortune");
+        let lights = self.select(pool(specific.map(|m| &m.light), &self.meanings.light), strategy, history, &mut rng, "No Light meaning");
+        let shadows = self.select(pool(specific.map(|m| &m.shadow), &self.meanings.shadow), strategy, history, &mut rng, "No Shadow meaning");
+
+        CardModel {
+            name: self.name.clone(),
+            title: self.title(),
+            suit: self.suit.clone(),
+            rank: self.rank,
+            reversed: self.reversed,
+            fortune: fortunes[0].clone(),
+            light: lights[0].clone(),
+            shadow: shadows[0].clone(),
+            fortunes,
+            lights,
+            shadows,
+            keyword: self.keyword(seed).to_string(),
+            keywords: self.keywords.clone(),
+            correspondences: None,
+        }
+    }
+
+    fn select(
+        &self,
+        pool: &[String],
+        strategy: MeaningStrategy,
+        history: &MeaningHistory,
+        rng: &mut impl rand::Rng,
+        fallback: &str
+        ) -> Vec<String> {
+        use rand::seq::SliceRandom;
+
+        let selected = match strategy {
+            MeaningStrategy::Random => pool.choose(rng).into_iter().cloned().collect(),
+            MeaningStrategy::First => pool.first().into_iter().cloned().collect(),
+            MeaningStrategy::Rotating => pool.iter()
+                .min_by_key(|meaning| history.last_shown(meaning))
+                .into_iter()
+                .cloned()
+                .collect(),
+            MeaningStrategy::Weighted => pool
+                .choose_weighted(rng, |meaning| self.weights.get(meaning).copied().unwrap_or(1))
+                .ok()
+                .into_iter()
+                .cloned()
+                .collect(),
+            MeaningStrategy::All => pool.to_vec(),
+        };
+        match selected.is_empty() {
+            true => vec![ fallback.to_string() ],
+            false => selected,
+        }
+    }
+
+    pub fn print(&self, seed: u64, mut writer: impl std::io::Write) {
+        let model = self.to_model(seed, None, MeaningStrategy::Random, &MeaningHistory::default());
+        let rendered = CARD_TEMPLATE.with(|template| template.render("card", &model));
+        write!(writer, "{}", rendered).unwrap();
+    }
+
+    /// Every meaning, keyword and correspondence of the card.
+    pub fn print_info(&self, mut writer: impl std::io::Write) {
+        let mut model = self.to_model(0, None, MeaningStrategy::All, &MeaningHistory::default());
+        model.correspondences = Some(self.correspondences.clone());
+        let rendered = CARD_TEMPLATE.with(|template| template.render("info", &model));
+        write!(writer, "{}", rendered).unwrap();
+    }
+}
+
+thread_local! {
+    /// The bundled templates, registered once for printing single cards.
+    static CARD_TEMPLATE: ReadingTemplate = ReadingTemplate::default();
+}
+
+/// Alternative spellings accepted when looking cards up by name.
+const NAME_SYNONYMS: [(&str, &str); 16] = [
+    ("1", "ace"), ("2", "two"), ("3", "three"), ("4", "four"), ("5", "five"),
+    ("6", "six"), ("7", "seven"), ("8", "eight"), ("9", "nine"), ("10", "ten"),
+    ("pentacles", "coins"), ("disks", "coins"), ("rods", "wands"), ("staves", "wands"),
+    ("chalices", "cups"), ("judgment", "judgement"),
+];
+/// Cost of a query word that matches nothing in a card's name.
+const UNMATCHED_COST: usize = 2;
+
+fn name_tokens(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty() && *t != "the" && *t != "of")
+        .map(|t| NAME_SYNONYMS.iter().find(|(from, _)| *from == t).map_or(t, |(_, to)| to).to_string())
+        .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![ i + 1 ];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + (ca != *cb) as usize;
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
 }
 
-#[derive(Deserialize, PartialEq, Debug)]
+#[derive(Deserialize, PartialEq, Debug, Clone)]
 pub struct Deck {
     cards: Vec<Card>,
-    pub name: String
+    pub name: String,
+    #[serde(default, deserialize_with = "crate::summary::deserialize_over_bundled")]
+    summary: SummaryNotes,
+    #[serde(default)]
+    combinations: Vec<Combination>,
 }
 
 use std::fmt;
@@ -55,14 +427,167 @@ impl StoredElement for Deck {
         serde_json::from_str(json).unwrap()
     } 
 }
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ShuffleMethod {
+    Random,
+    Riffle,
+    Overhand,
+    Pile,
+}
+
+impl std::str::FromStr for ShuffleMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ShuffleMethod, String> {
+        match s.to_lowercase().as_str() {
+            "random" => Ok(ShuffleMethod::Random),
+            "riffle" => Ok(ShuffleMethod::Riffle),
+            "overhand" => Ok(ShuffleMethod::Overhand),
+            "pile" => Ok(ShuffleMethod::Pile),
+            _ => Err(format!("Unknown shuffle method: {}", s)),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct ShuffleOptions {
+    pub method: ShuffleMethod,
+    pub passes: u32,
+    pub cut: Option<usize>,
+    pub jumper_chance: f64,
+    pub reversal_chance: f64,
+}
+
+impl Default for ShuffleOptions {
+    fn default() -> ShuffleOptions {
+        ShuffleOptions {
+            method: ShuffleMethod::Random,
+            passes: 1,
+            cut: None,
+            jumper_chance: 0.0,
+            reversal_chance: 0.0,
+        }
+    }
+}
+
+// Most cards that can fly out of the deck during a single shuffle
+const MAX_JUMPERS: usize = 3;
+// Number of piles dealt out for a single pass of the pile shuffle
+const PILE_COUNT: usize = 6;
+// Chance that an overhand shuffle breaks the packet after any given card
+const OVERHAND_BREAK_CHANCE: f64 = 0.2;
+
+fn riffle<T>(cards: Vec<T>, rng: &mut impl rand::Rng) -> Vec<T> {
+    // Gilbert-Shannon-Reeds: cut binomially, then drop cards from each half
+    // with probability proportional to the size of that half
+    let split = (0..cards.len()).filter(|_| rng.gen_bool(0.5)).count();
+    let mut right = cards;
+    let left = right.drain(..split).collect::<Vec<T>>();
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    let mut left_count = split;
+    let mut right_count = right.len();
+    let mut result = Vec::with_capacity(left_count + right_count);
+    while left_count + right_count > 0 {
+        if rng.gen_range(0, left_count + right_count) < left_count {
+            result.push(left.next().unwrap());
+            left_count -= 1;
+        } else {
+            result.push(right.next().unwrap());
+            right_count -= 1;
+        }
+    }
+    result
+}
+
+fn overhand<T>(cards: Vec<T>, rng: &mut impl rand::Rng) -> Vec<T> {
+    // Packets are slipped off the top and stacked, reversing packet order
+    let mut packets: Vec<Vec<T>> = Vec::new();
+    let mut current = Vec::new();
+    for card in cards {
+        current.push(card);
+        if rng.gen_bool(OVERHAND_BREAK_CHANCE) {
+            packets.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        packets.push(current);
+    }
+    packets.into_iter().rev().flatten().collect()
+}
+
+fn pile<T>(cards: Vec<T>, rng: &mut impl rand::Rng) -> Vec<T> {
+    use rand::seq::SliceRandom;
+
+    // Cards are dealt round-robin onto piles, which are gathered in random order
+    let mut piles: Vec<Vec<T>> = (0..PILE_COUNT).map(|_| Vec::new()).collect();
+    for (index, card) in cards.into_iter().enumerate() {
+        piles[index % PILE_COUNT].push(card);
+    }
+    for dealt in piles.iter_mut() {
+        dealt.reverse();
+    }
+    piles.shuffle(rng);
+    piles.into_iter().flatten().collect()
+}
+
 impl Deck {
     pub fn shuffle_deck(&mut self, seed: u64) {
-        use rand::SeedableRng;
+        self.shuffle_deck_with(seed, &ShuffleOptions::default());
+    }
+
+    /// Shuffles the deck and returns the number of jumpers, which are moved
+    /// to the top of the deck ahead of the cards dealt into the spread.
+    pub fn shuffle_deck_with(&mut self, seed: u64, options: &ShuffleOptions) -> usize {
+        self.shuffle_to_deal(seed, options, 0)
+    }
+
+    /// As `shuffle_deck_with`, but leaves at least `dealt` cards after the
+    /// jumpers so the spread can still be dealt.
+    pub fn shuffle_to_deal(&mut self, seed: u64, options: &ShuffleOptions, dealt: usize) -> usize {
+        use rand::{Rng, SeedableRng};
         use rand::rngs;
         use rand::seq::SliceRandom;
 
         let mut rng = rngs::StdRng::seed_from_u64(seed);
-        self.cards.shuffle(&mut rng);
+        for _ in 0..options.passes {
+            match options.method {
+                ShuffleMethod::Random => self.cards.shuffle(&mut rng),
+                ShuffleMethod::Riffle => {
+                    self.cards = riffle(std::mem::take(&mut self.cards), &mut rng)
+                },
+                ShuffleMethod::Overhand => {
+                    self.cards = overhand(std::mem::take(&mut self.cards), &mut rng)
+                },
+                ShuffleMethod::Pile => {
+                    self.cards = pile(std::mem::take(&mut self.cards), &mut rng)
+                },
+            }
+        }
+        for card in self.cards.iter_mut() {
+            card.reversed = options.reversal_chance > 0.0 && rng.gen_bool(options.reversal_chance);
+        }
+        let mut jumpers = 0;
+        if options.jumper_chance > 0.0 {
+            while jumpers < MAX_JUMPERS.min(self.cards.len().saturating_sub(dealt)) && rng.gen_bool(options.jumper_chance) {
+                let jumped = self.cards.remove(rng.gen_range(jumpers, self.cards.len()));
+                self.cards.insert(jumpers, jumped);
+                jumpers += 1;
+            }
+        }
+        if let Some(position) = options.cut {
+            // A cut below the cards left after the jumpers leaves them as they are
+            let remaining = &mut self.cards[jumpers..];
+            let position = position.min(remaining.len());
+            remaining.rotate_left(position);
+        }
+        jumpers
+    }
+
+    /// Shuffles and deals a single card, passing over any jumpers.
+    pub fn deal_one(&mut self, seed: u64, options: &ShuffleOptions) -> &Card {
+        let jumpers = self.shuffle_to_deal(seed, options, 1);
+        &self.draw(jumpers + 1)[jumpers]
     }
 
     pub fn draw(&self, count: usize) -> &[Card] {
@@ -71,6 +596,102 @@ impl Deck {
         }
         &self.cards[0..count]
     }
+
+    /// The card with the given name, ignoring case.
+    pub fn find(&self, name: &str) -> Option<&Card> {
+        self.cards.iter().find(|c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Cards whose names best match `query`, allowing for typos, missing
+    /// words, digits for numbers and other suit names. Ties are all returned.
+    pub fn lookup(&self, query: &str) -> Vec<&Card> {
+        if let Some(card) = self.find(query) {
+            return vec![ card ];
+        }
+        let query = name_tokens(query);
+        let scored: Vec<((usize, usize), &Card)> = self.cards.iter()
+            .filter_map(|card| {
+                let name = name_tokens(&card.name);
+                let mut matched = 0;
+                let mut cost = 0;
+                for token in &query {
+                    let distance = name.iter()
+                        .map(|n| match token.len() >= 3 && n.starts_with(token.as_str()) {
+                            true => 0,
+                            false => edit_distance(token, n),
+                        })
+                        .min();
+                    match distance {
+                        Some(d) if d <= (token.len() / 4).max(1) => {
+                            matched += 1;
+                            cost += d;
+                        },
+                        _ => cost += UNMATCHED_COST,
+                    }
+                }
+                match matched {
+                    0 => None,
+                    _ => Some(((cost, name.len().saturating_sub(matched)), card)),
+                }
+            })
+            .collect();
+        let best = scored.iter().map(|(score, _)| *score).min();
+        scored.into_iter()
+            .filter(|(score, _)| Some(*score) == best)
+            .map(|(_, card)| card)
+            .collect()
+    }
+
+    /// Every card, grouped by suit in deck order and sorted by rank.
+    pub fn print_list(&self, mut writer: impl std::io::Write) {
+        let mut suits: Vec<&str> = Vec::new();
+        for card in &self.cards {
+            if !suits.contains(&card.suit.as_str()) {
+                suits.push(&card.suit);
+            }
+        }
+        for (i, suit) in suits.iter().enumerate() {
+            if i > 0 {
+                writeln!(writer).unwrap();
+            }
+            writeln!(writer, "{}", suit).unwrap();
+            writeln!(writer, "{}", "-".repeat(suit.len())).unwrap();
+            let mut cards: Vec<&Card> = self.cards.iter().filter(|c| c.suit == *suit).collect();
+            cards.sort_by_key(|c| c.rank);
+            for card in cards {
+                writeln!(writer, "{:>2}  {}", card.rank, card.name).unwrap();
+            }
+        }
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// The major arcana card with the given rank, if the deck has one.
+    pub fn major(&self, rank: u8) -> Option<&Card> {
+        self.cards.iter().find(|c| c.is_major() && c.rank == rank)
+    }
+
+    pub fn summary_notes(&self) -> &SummaryNotes {
+        &self.summary
+    }
+
+    pub fn combinations(&self) -> &[Combination] {
+        &self.combinations
+    }
+
+    pub fn add_combinations(&mut self, combinations: Vec<Combination>) {
+        self.combinations.extend(combinations);
+    }
+
+    /// Cards left in the deck after the top `count` have been drawn.
+    pub fn remaining(&self, count: usize) -> &[Card] {
+        if self.cards.len() < count {
+            panic!("Attempted to draw more cards than are in deck.");
+        }
+        &self.cards[count..]
+    }
 }
 
 pub mod test_utils;
@@ -82,8 +703,17 @@ mod tests {
         return_test_card
     };
     use crate::deck::{
-        Deck
+        Correspondences,
+        Decan,
+        Deck,
+        MeaningHistory,
+        MeaningStrategy,
+        PositionCategory,
+        ShuffleMethod,
+        ShuffleOptions
     };
+    use crate::summary::SummaryNotes;
+    use crate::journal::{PositionRecord, ReadingRecord};
 
     #[test]
     fn display_card() {
@@ -104,6 +734,100 @@ Shadow: shadow_meaning
             )
     }
 
+    #[test]
+    fn card_oneline() {
+        let mut test_card = return_test_card();
+        assert_eq!(test_card.oneline(1), "\u{1F0A0} test_name (test_keyword1): test_fortune1");
+
+        test_card.suit = "cups".to_string();
+        test_card.rank = 13;
+        test_card.reversed = true;
+        test_card.fortune_telling = vec![ "word ".repeat(30) ];
+        let line = test_card.oneline(1);
+        assert!(line.starts_with("\u{1F0BD} test_name (Reversed) ("));
+        assert!(line.ends_with("word\u{2026}"));
+        assert!(line.chars().count() <= 80);
+    }
+
+    #[test]
+    fn card_info() {
+        let mut test_card = return_test_card();
+        test_card.correspondences = serde_json::from_str(r#"{
+            "element": "water",
+            "zodiac": "Cancer",
+            "decan": { "start": "06-21", "end": "07-01" }
+        }"#).unwrap();
+        assert_eq!(test_card.correspondences().decan, Some(Decan {
+            start: "06-21".to_string(),
+            end: "07-01".to_string(),
+        }));
+        assert_eq!(test_card.correspondences().planet, None);
+
+        let mut test_result = Vec::new();
+        test_card.print_info(&mut test_result);
+        let target_output = r#"test_name
+---------
+test_fortune
+test_fortune1
+Light: light_meaning
+Light: light_meaning2
+Shadow: shadow_meaning
+Shadow: shadow_meaning2
+Element: water
+Zodiac: Cancer
+Decan: 06-21 to 07-01
+Keywords: test_keyword, test_keyword1
+"#;
+        assert_eq!(String::from_utf8(test_result).unwrap(), target_output);
+
+        let test_deck = Deck::new_from_json(&return_test_deck());
+        assert_eq!(test_deck.find("TEST_NAME1").map(|c| c.name()), Some("test_name1"));
+        assert_eq!(test_deck.find("test_name1").unwrap().correspondences(), &Correspondences::default());
+    }
+
+    #[test]
+    fn deck_lookup() {
+        let deck = Deck::new_from_json(include_str!("../default_files/included_decks/default_deck.json"));
+        let lookup = |query| deck.lookup(query).iter().map(|c| c.name()).collect::<Vec<&str>>();
+        assert_eq!(lookup("the fool"), vec!["The Fool"]);
+        assert_eq!(lookup("2 of pentacles"), vec!["two of coins"]);
+        assert_eq!(lookup("queen cup"), vec!["queen of cups"]);
+        assert_eq!(lookup("hierophnat"), vec!["The Pope/Hierophant"]);
+        assert_eq!(lookup("emperor"), vec!["The Emperor"]);
+        assert_eq!(lookup("wheel of fortune"), vec!["The Wheel"]);
+        assert_eq!(lookup("judgment"), vec!["Judgement"]);
+        assert_eq!(lookup("king").len(), 4);
+        assert!(lookup("xyzzy").is_empty());
+    }
+
+    #[test]
+    fn bundled_elemental_trumps() {
+        let deck = Deck::new_from_json(include_str!("../default_files/included_decks/default_deck.json"));
+        for (rank, element) in &[(0, "air"), (12, "water"), (20, "fire")] {
+            let correspondences = deck.major(*rank).unwrap().correspondences();
+            assert_eq!(correspondences.element.as_deref(), Some(*element));
+            assert_eq!(correspondences.planet, None);
+        }
+    }
+
+    #[test]
+    fn deck_list() {
+        let mut test_result = Vec::new();
+        Deck::new_from_json(&return_test_deck()).print_list(&mut test_result);
+        assert_eq!(String::from_utf8(test_result).unwrap(), r#"test_suit
+---------
+ 0  test_name
+
+test_suit1
+----------
+ 1  test_name1
+
+test_suit2
+----------
+ 2  test_name2
+"#);
+    }
+
     #[test]
     fn deck_deserial() {
         let mut test_deck = Deck::new_from_json(&return_test_deck());
@@ -112,6 +836,8 @@ Shadow: shadow_meaning
         let manual_deck = Deck {
             name: "test deck".to_string(),
             cards: vec![ return_test_card() ],
+            summary: SummaryNotes::default(),
+            combinations: Vec::new(),
         };
 
         assert_eq!(manual_deck, test_deck);
@@ -124,6 +850,14 @@ Shadow: shadow_meaning
         assert_eq!(test_deck.draw(2).len(), 2);
     }
 
+    #[test]
+    fn deck_remaining() {
+        let test_deck = Deck::new_from_json(&return_test_deck());
+        assert_eq!(test_deck.remaining(1).len(), 2);
+        assert_eq!(test_deck.remaining(1)[1].name, "test_name2");
+        assert!(test_deck.remaining(3).is_empty());
+    }
+
     #[test]
     #[should_panic]
     fn deck_draw_too_many() {
@@ -151,6 +885,215 @@ Shadow: shadow_meaning
         assert_ne!(first_draw, second_draw);
     }
 
+    #[test]
+    fn shuffle_method_parse() {
+        assert_eq!("Riffle".parse::<ShuffleMethod>(), Ok(ShuffleMethod::Riffle));
+        assert_eq!("pile".parse::<ShuffleMethod>(), Ok(ShuffleMethod::Pile));
+        assert!("spin".parse::<ShuffleMethod>().is_err());
+    }
+
+    #[test]
+    fn deck_repeatable_shuffle_methods() {
+        for method in &[ShuffleMethod::Riffle, ShuffleMethod::Overhand, ShuffleMethod::Pile] {
+            let options = ShuffleOptions {
+                method: *method,
+                passes: 3,
+                cut: None,
+                jumper_chance: 0.0,
+                reversal_chance: 0.0,
+            };
+            let mut test_deck = Deck::new_from_json(&return_test_deck());
+            let mut test_deck2 = Deck::new_from_json(&return_test_deck());
+            test_deck.shuffle_deck_with(7, &options);
+            test_deck2.shuffle_deck_with(7, &options);
+            assert_eq!(test_deck.draw(3), test_deck2.draw(3));
+        }
+    }
+
+    #[test]
+    fn deck_shuffle_keeps_cards() {
+        let options = ShuffleOptions {
+            method: ShuffleMethod::Riffle,
+            passes: 7,
+            cut: Some(1),
+            jumper_chance: 0.0,
+            reversal_chance: 0.0,
+        };
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        test_deck.shuffle_deck_with(3, &options);
+        let mut names = test_deck.cards.iter().map(|c| c.name.clone()).collect::<Vec<String>>();
+        names.sort();
+        assert_eq!(names, vec!["test_name", "test_name1", "test_name2"]);
+    }
+
+    #[test]
+    fn deck_cut() {
+        let options = ShuffleOptions {
+            method: ShuffleMethod::Random,
+            passes: 0,
+            cut: Some(1),
+            jumper_chance: 0.0,
+            reversal_chance: 0.0,
+        };
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        test_deck.shuffle_deck_with(1, &options);
+        assert_eq!(test_deck.draw(1)[0].name, "test_name1");
+        assert_eq!(test_deck.cards[2].name, "test_name");
+    }
+
+    #[test]
+    fn deck_cut_out_of_range() {
+        let mut cut_deck = Deck::new_from_json(&return_test_deck());
+        cut_deck.shuffle_deck_with(1, &ShuffleOptions { cut: Some(3), ..ShuffleOptions::default() });
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        test_deck.shuffle_deck_with(1, &ShuffleOptions::default());
+        assert_eq!(cut_deck.draw(3), test_deck.draw(3));
+    }
+
+    #[test]
+    fn card_model_matches_print() {
+        let test_card = return_test_card();
+        let model = test_card.to_model(1, None, MeaningStrategy::Random, &MeaningHistory::default());
+        assert_eq!(model.fortune, "test_fortune1");
+        assert_eq!(model.light, "light_meaning2");
+        assert_eq!(model.shadow, "shadow_meaning");
+        assert!(test_card.keywords.contains(&model.keyword));
+    }
+
+    #[test]
+    fn card_category_meanings() {
+        let test_deck = Deck::new_from_json(&return_test_deck().replacen(
+            r#""fortune_telling": [
+              "test_fortune1"
+            ]"#,
+            r#""fortune_telling": [
+              "test_fortune1"
+            ],
+            "category_meanings": {
+              "past": { "fortune_telling": [ "past_fortune" ] },
+              "advice": { "light": [ "advice_light" ] }
+            }"#,
+            1
+            ));
+        let test_card = &test_deck.cards[1];
+        let past = test_card.to_model(1, Some(PositionCategory::Past), MeaningStrategy::Random, &MeaningHistory::default());
+        assert_eq!(past.fortune, "past_fortune");
+        assert_eq!(past.light, "light_meaning1");
+        let advice = test_card.to_model(1, Some(PositionCategory::Advice), MeaningStrategy::Random, &MeaningHistory::default());
+        assert_eq!(advice.fortune, "test_fortune1");
+        assert_eq!(advice.light, "advice_light");
+        let outcome = test_card.to_model(1, Some(PositionCategory::Outcome), MeaningStrategy::Random, &MeaningHistory::default());
+        assert_eq!(outcome, test_card.to_model(1, None, MeaningStrategy::Random, &MeaningHistory::default()));
+    }
+
+    #[test]
+    fn meaning_strategy_parse() {
+        assert_eq!("Rotating".parse::<MeaningStrategy>(), Ok(MeaningStrategy::Rotating));
+        assert!("best".parse::<MeaningStrategy>().is_err());
+    }
+
+    #[test]
+    fn card_meaning_strategies() {
+        let test_card = return_test_card();
+        let history = MeaningHistory::default();
+
+        let first = test_card.to_model(1, None, MeaningStrategy::First, &history);
+        assert_eq!(first.fortunes, vec!["test_fortune"]);
+        assert_eq!(first.light, "light_meaning");
+
+        let all = test_card.to_model(1, None, MeaningStrategy::All, &history);
+        assert_eq!(all.fortunes, vec!["test_fortune", "test_fortune1"]);
+        assert_eq!(all.shadows, vec!["shadow_meaning", "shadow_meaning2"]);
+        assert_eq!(all.fortune, "test_fortune");
+
+        let mut weighted_card = return_test_card();
+        weighted_card.weights.insert("test_fortune".to_string(), 0);
+        for seed in 0..10 {
+            let weighted = weighted_card.to_model(seed, None, MeaningStrategy::Weighted, &history);
+            assert_eq!(weighted.fortune, "test_fortune1");
+        }
+    }
+
+    #[test]
+    fn card_rotating_meanings() {
+        let test_card = return_test_card();
+        let records = vec![
+            ReadingRecord {
+                timestamp: 0,
+                seed: 0,
+                deck: "test deck".to_string(),
+                spread: "test spread".to_string(),
+                positions: vec![ PositionRecord {
+                    position: "test position 1".to_string(),
+                    card: "test_name".to_string(),
+                    reversed: false,
+                    shown_meanings: vec![ "test_fortune".to_string(), "light_meaning2".to_string() ],
+                }],
+                jumpers: Vec::new(),
+                question: None,
+            },
+        ];
+        let history = MeaningHistory::from_records(&records);
+        let rotating = test_card.to_model(1, None, MeaningStrategy::Rotating, &history);
+        assert_eq!(rotating.fortune, "test_fortune1");
+        assert_eq!(rotating.light, "light_meaning");
+        assert_eq!(rotating.shadow, "shadow_meaning");
+    }
+
+    #[test]
+    fn display_reversed_card() {
+        let mut test_result = Vec::new();
+        let mut test_card = return_test_card();
+        test_card.reversed = true;
+        test_card.print(1, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        assert!(test_output.starts_with("test_name (Reversed)\n--------------------\n"));
+    }
+
+    #[test]
+    fn deck_reversals() {
+        let mut options = ShuffleOptions {
+            reversal_chance: 1.0,
+            ..ShuffleOptions::default()
+        };
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        test_deck.shuffle_deck_with(1, &options);
+        assert!(test_deck.cards.iter().all(|card| card.is_reversed()));
+
+        options.reversal_chance = 0.0;
+        test_deck.shuffle_deck_with(1, &options);
+        assert!(test_deck.cards.iter().all(|card| !card.is_reversed()));
+    }
+
+    #[test]
+    fn deck_jumpers() {
+        let mut options = ShuffleOptions::default();
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        assert_eq!(test_deck.shuffle_deck_with(1, &options), 0);
+
+        options.jumper_chance = 1.0;
+        options.cut = Some(1);
+        let mut test_deck2 = Deck::new_from_json(&return_test_deck());
+        assert_eq!(test_deck2.shuffle_deck_with(1, &options), 3);
+        assert_eq!(test_deck2.cards.len(), 3);
+
+        let mut test_deck3 = Deck::new_from_json(&return_test_deck());
+        assert_eq!(test_deck3.shuffle_to_deal(1, &options, 2), 1);
+        assert_eq!(test_deck3.shuffle_to_deal(1, &options, 3), 0);
+    }
+
+    #[test]
+    fn deck_deal_one_skips_jumpers() {
+        let options = ShuffleOptions { jumper_chance: 1.0, ..ShuffleOptions::default() };
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let dealt = test_deck.deal_one(1, &options).name().to_string();
+
+        let mut jumped_deck = Deck::new_from_json(&return_test_deck());
+        let jumpers = jumped_deck.shuffle_to_deal(1, &options, 1);
+        assert_eq!(jumpers, 2);
+        assert!(jumped_deck.draw(jumpers).iter().all(|card| card.name() != dealt));
+    }
+
 
 }
 
@@ -1,7 +1,9 @@
 #[cfg(test)]
 pub mod utils {
+use std::collections::HashMap;
 use crate::deck::{
     Card,
+    Correspondences,
     Meaning
 };
 
@@ -30,6 +32,11 @@ pub fn return_test_card() -> Card {
             String::from("test_fortune"),
             String::from("test_fortune1")
         ],
+        category_meanings: HashMap::new(),
+        weights: HashMap::new(),
+        correspondences: Correspondences::default(),
+        polarity: None,
+        reversed: false,
     }
 }
 
@@ -1,7 +1,7 @@
 extern crate directories;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use directories::ProjectDirs;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 
 struct DefaultFile {
     filename: String,
@@ -11,12 +11,14 @@ struct DefaultFile {
 pub enum ElementType {
     Spread,
     Deck,
+    Combinations,
+    Journey,
 }
 
 fn return_default_files_dir() -> std::io::Result<PathBuf> {
     match ProjectDirs::from("", "terminal_tarot", "terminal_tarot") {
         Some(path) => Ok(path.data_dir().to_path_buf()),
-        None => Err(Error::new(ErrorKind::Other, "couldn't determine data directory")),
+        None => Err(Error::other("couldn't determine data directory")),
     }
 }
 
@@ -26,6 +28,16 @@ fn construct_dir_path(new_dir_name: &str) -> std::io::Result<PathBuf>{
     Ok(data_dir)
 }
 
+/// Path of a file kept directly in the data directory, creating the directory if needed.
+pub fn data_file_path(filename: &str) -> std::io::Result<PathBuf> {
+    let mut data_dir = return_default_files_dir()?;
+    if !data_dir.exists() {
+        std::fs::create_dir_all(&data_dir)?;
+    }
+    data_dir.push(filename);
+    Ok(data_dir)
+}
+
 pub fn write_default_files(element: ElementType, overwrite: bool) -> std::io::Result<PathBuf> {
     let standard_deck = DefaultFile {
         filename: "standard_deck.json".to_string(),
@@ -52,8 +64,20 @@ pub fn write_default_files(element: ElementType, overwrite: bool) -> std::io::Re
         file_contents: include_str!("included_spreads/basic_three_card.json"),
     };
 
+    let standard_combinations = DefaultFile {
+        filename: "standard_combinations.json".to_string(),
+        file_contents: include_str!("included_combinations/standard_combinations.json"),
+    };
+
+    let fools_journey = DefaultFile {
+        filename: "fools_journey.json".to_string(),
+        file_contents: include_str!("included_journeys/fools_journey.json"),
+    };
+
     let default_decks = vec![standard_deck];
     let default_spreads = vec![debug_spread, celtic_cross, single_card, basic_three];
+    let default_combinations = vec![standard_combinations];
+    let default_journeys = vec![fools_journey];
 
     match element {
         ElementType::Deck => {
@@ -66,20 +90,30 @@ pub fn write_default_files(element: ElementType, overwrite: bool) -> std::io::Re
             check_or_write_dir(&mut spread_path, default_spreads, &overwrite)?;
             Ok(spread_path)
         },
+        ElementType::Combinations => {
+            let mut combinations_path = construct_dir_path("default_combinations")?;
+            check_or_write_dir(&mut combinations_path, default_combinations, &overwrite)?;
+            Ok(combinations_path)
+        },
+        ElementType::Journey => {
+            let mut journey_path = construct_dir_path("default_journeys")?;
+            check_or_write_dir(&mut journey_path, default_journeys, &overwrite)?;
+            Ok(journey_path)
+        },
     }
 }
 
-fn check_or_write_dir(target_dir: &mut PathBuf, files_to_write: Vec<DefaultFile>, overwrite: &bool) ->  std::io::Result<()>{
-    if target_dir.exists() == false {
+fn check_or_write_dir(target_dir: &mut Path, files_to_write: Vec<DefaultFile>, overwrite: &bool) ->  std::io::Result<()>{
+    if !target_dir.exists() {
         std::fs::create_dir_all(&target_dir)?;
     }
     write_files(target_dir, files_to_write, overwrite)?;
     Ok(())
 }
 
-fn write_files(target_dir: &mut PathBuf, files_to_write: Vec<DefaultFile>, overwrite: &bool) -> std::io::Result<()>{
+fn write_files(target_dir: &mut Path, files_to_write: Vec<DefaultFile>, overwrite: &bool) -> std::io::Result<()>{
     for file in files_to_write.iter() {
-        let mut file_path = target_dir.clone();
+        let mut file_path = target_dir.to_path_buf();
         file_path.push(&file.filename);
         if !file_path.exists() | overwrite {
             std::fs::write(file_path, file.file_contents)?;
@@ -0,0 +1,89 @@
+use serde::Serialize;
+
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+pub enum Element {
+    Fire,
+    Water,
+    Air,
+    Earth,
+}
+
+impl std::str::FromStr for Element {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Element, String> {
+        match s.to_lowercase().as_str() {
+            "fire" => Ok(Element::Fire),
+            "water" => Ok(Element::Water),
+            "air" => Ok(Element::Air),
+            "earth" => Ok(Element::Earth),
+            _ => Err(format!("Unknown element: {}", s)),
+        }
+    }
+}
+
+#[derive(Serialize, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Dignity {
+    Strengthening,
+    Weakening,
+    Neutral,
+}
+
+impl Dignity {
+    /// Golden Dawn dignity of one element beside another: the same or
+    /// friendly elements strengthen, contrary elements weaken.
+    pub fn between(card: Element, neighbour: Element) -> Dignity {
+        use Element::*;
+
+        match (card, neighbour) {
+            (a, b) if a == b => Dignity::Strengthening,
+            (Fire, Air) | (Air, Fire) | (Water, Earth) | (Earth, Water) => Dignity::Strengthening,
+            (Fire, Water) | (Water, Fire) | (Air, Earth) | (Earth, Air) => Dignity::Weakening,
+            _ => Dignity::Neutral,
+        }
+    }
+
+    /// Overall dignity of a card given its dignity towards each neighbour.
+    pub fn overall(dignities: &[Dignity]) -> Dignity {
+        let strong = dignities.iter().filter(|d| **d == Dignity::Strengthening).count();
+        let weak = dignities.iter().filter(|d| **d == Dignity::Weakening).count();
+        match strong.cmp(&weak) {
+            std::cmp::Ordering::Greater => Dignity::Strengthening,
+            std::cmp::Ordering::Less => Dignity::Weakening,
+            std::cmp::Ordering::Equal => Dignity::Neutral,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dignities::{Dignity, Element};
+
+    #[test]
+    fn element_parse() {
+        assert_eq!("Fire".parse::<Element>(), Ok(Element::Fire));
+        assert_eq!("earth".parse::<Element>(), Ok(Element::Earth));
+        assert!("Aether".parse::<Element>().is_err());
+    }
+
+    #[test]
+    fn dignity_between() {
+        assert_eq!(Dignity::between(Element::Water, Element::Water), Dignity::Strengthening);
+        assert_eq!(Dignity::between(Element::Fire, Element::Air), Dignity::Strengthening);
+        assert_eq!(Dignity::between(Element::Earth, Element::Water), Dignity::Strengthening);
+        assert_eq!(Dignity::between(Element::Fire, Element::Water), Dignity::Weakening);
+        assert_eq!(Dignity::between(Element::Earth, Element::Air), Dignity::Weakening);
+        assert_eq!(Dignity::between(Element::Fire, Element::Earth), Dignity::Neutral);
+        assert_eq!(Dignity::between(Element::Air, Element::Water), Dignity::Neutral);
+    }
+
+    #[test]
+    fn dignity_overall() {
+        use Dignity::*;
+        assert_eq!(Dignity::overall(&[Strengthening, Neutral]), Strengthening);
+        assert_eq!(Dignity::overall(&[Strengthening, Weakening, Weakening]), Weakening);
+        assert_eq!(Dignity::overall(&[Strengthening, Weakening]), Neutral);
+        assert_eq!(Dignity::overall(&[]), Neutral);
+    }
+}
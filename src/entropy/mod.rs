@@ -0,0 +1,92 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+/// Number of times the user shuffles in ritual mode.
+pub const RITUAL_PRESSES: usize = 7;
+
+/// Where the randomness behind a reading comes from.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum EntropySource {
+    /// The `--seed` value, or the current time when none is given.
+    Seed,
+    /// The operating system's random number generator.
+    Os,
+    /// The seed mixed with the timing of the user's own shuffling.
+    Ritual,
+}
+
+impl std::str::FromStr for EntropySource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<EntropySource, String> {
+        match s.to_lowercase().as_str() {
+            "seed" => Ok(EntropySource::Seed),
+            "os" => Ok(EntropySource::Os),
+            "ritual" => Ok(EntropySource::Ritual),
+            other => Err(format!("Unknown entropy source: {}", other)),
+        }
+    }
+}
+
+pub fn os_seed() -> u64 {
+    use rand::RngCore;
+    rand::rngs::OsRng.next_u64()
+}
+
+/// Mixes the gaps between keystrokes into a seed.
+pub fn mix_timings(seed: u64, timings: &[Duration]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    for timing in timings {
+        timing.as_nanos().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Asks the user to press ENTER each time they shuffle, returning the time
+/// taken over each shuffle.
+pub fn ritual_timings(mut reader: impl std::io::BufRead, mut writer: impl std::io::Write, presses: usize) -> Vec<Duration> {
+    writeln!(writer, "Hold your question in mind and press ENTER each time you shuffle the deck").unwrap();
+    let mut timings = Vec::new();
+    let mut last = Instant::now();
+    for press in 1..=presses {
+        write!(writer, "Shuffle {} of {}", press, presses).unwrap();
+        writer.flush().unwrap();
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let now = Instant::now();
+        timings.push(now - last);
+        last = now;
+    }
+    writeln!(writer).unwrap();
+    timings
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::entropy::{mix_timings, os_seed, ritual_timings, EntropySource};
+
+    #[test]
+    fn entropy_sources() {
+        assert_eq!("OS".parse(), Ok(EntropySource::Os));
+        assert_eq!("ritual".parse(), Ok(EntropySource::Ritual));
+        assert!("dice".parse::<EntropySource>().is_err());
+        assert_ne!(os_seed(), os_seed());
+    }
+
+    #[test]
+    fn ritual_mixing() {
+        let timings = vec![ Duration::from_millis(350), Duration::from_millis(420) ];
+        assert_eq!(mix_timings(1, &timings), mix_timings(1, &timings));
+        assert_ne!(mix_timings(1, &timings), mix_timings(1, &timings[..1]));
+        assert_ne!(mix_timings(1, &timings), mix_timings(2, &timings));
+
+        let mut test_result = Vec::new();
+        let timings = ritual_timings(&b"\n\nextra\n"[..], &mut test_result, 2);
+        assert_eq!(timings.len(), 2);
+        let output = String::from_utf8(test_result).unwrap();
+        assert!(output.ends_with("Shuffle 1 of 2Shuffle 2 of 2\n"));
+    }
+}
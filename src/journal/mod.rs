@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const JOURNAL_FILENAME: &str = "journal.jsonl";
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct PositionRecord {
+    pub position: String,
+    pub card: String,
+    #[serde(default)]
+    pub reversed: bool,
+    #[serde(default)]
+    pub shown_meanings: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct ReadingRecord {
+    pub timestamp: u64,
+    pub seed: u64,
+    pub deck: String,
+    pub spread: String,
+    pub positions: Vec<PositionRecord>,
+    #[serde(default)]
+    pub jumpers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+}
+
+/// Appends a reading to the journal, one JSON object per line.
+pub fn append_reading(path: &Path, record: &ReadingRecord) -> std::io::Result<()> {
+    use std::fs::OpenOptions;
+    use std::io::prelude::*;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let line = serde_json::to_string(record)?;
+    writeln!(file, "{}", line)
+}
+
+/// Every reading in the journal. Lines that can't be read are skipped with a
+/// warning rather than losing the rest of the journal.
+pub fn load_readings(path: &Path) -> std::io::Result<Vec<ReadingRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = std::fs::read_to_string(path)?;
+    let mut readings = Vec::new();
+    for (number, line) in contents.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        match serde_json::from_str(line) {
+            Ok(reading) => readings.push(reading),
+            Err(e) => eprintln!("Skipping line {} of {}: {}", number + 1, path.display(), e),
+        }
+    }
+    Ok(readings)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::journal::{
+        append_reading,
+        load_readings,
+        PositionRecord,
+        ReadingRecord
+    };
+
+    #[test]
+    fn journal_round_trip() {
+        use std::io::Write;
+
+        let mut path = std::env::temp_dir();
+        path.push(format!("terminal_tarot_journal_test_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let record = ReadingRecord {
+            timestamp: 1,
+            seed: 2,
+            deck: "test deck".to_string(),
+            spread: "test spread".to_string(),
+            positions: vec![ PositionRecord {
+                position: "test position 1".to_string(),
+                card: "test_name".to_string(),
+                reversed: true,
+                shown_meanings: vec![ "test_fortune".to_string() ],
+            }],
+            jumpers: vec![ "test_name1".to_string() ],
+            question: Some("test question?".to_string()),
+        };
+        append_reading(&path, &record).unwrap();
+        std::fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"timestamp\": \n").unwrap();
+        append_reading(&path, &record).unwrap();
+        let loaded = load_readings(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, vec![record.clone(), record]);
+    }
+
+    #[test]
+    fn journal_missing_file() {
+        let mut path = std::env::temp_dir();
+        path.push("terminal_tarot_journal_missing.jsonl");
+        assert_eq!(load_readings(&path).unwrap(), Vec::new());
+    }
+}
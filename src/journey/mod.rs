@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::deck::Deck;
+use crate::stored_element::{SavedState, StoredElement};
+
+pub const PROGRESS_FILENAME: &str = "journey_progress.json";
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct JourneyStep {
+    rank: u8,
+    narrative: String,
+}
+
+/// A guided walk through the major arcana, with narrative linking each card to the next.
+#[derive(Deserialize, PartialEq, Debug)]
+pub struct Journey {
+    pub name: String,
+    #[serde(default)]
+    intro: String,
+    steps: Vec<JourneyStep>,
+    #[serde(default)]
+    outro: String,
+}
+
+impl fmt::Display for Journey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl StoredElement for Journey {
+    fn new_from_json(json: &str) -> Journey {
+        let mut journey: Journey = serde_json::from_str(json).expect("Failed to deserialize journey");
+        journey.steps.sort_by_key(|step| step.rank);
+        journey
+    }
+}
+
+impl Journey {
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn intro(&self) -> &str {
+        &self.intro
+    }
+
+    pub fn outro(&self) -> &str {
+        &self.outro
+    }
+
+    /// Ranks of the steps whose major arcana card the deck lacks.
+    pub fn missing_ranks(&self, deck: &Deck) -> Vec<u8> {
+        self.steps.iter()
+            .map(|step| step.rank)
+            .filter(|rank| deck.major(*rank).is_none())
+            .collect()
+    }
+
+    /// Prints the narrative for a step followed by everything the deck says about its card.
+    pub fn print_step(&self, index: usize, deck: &Deck, mut writer: impl std::io::Write) {
+        let step = &self.steps[index];
+        let card = deck.major(step.rank)
+            .unwrap_or_else(|| panic!("{} has no major arcana card numbered {}", deck.name, step.rank));
+        let title = format!("Stage {} of {}", index + 1, self.steps.len());
+        writeln!(writer, "{}", title).unwrap();
+        writeln!(writer, "{}", "=".repeat(title.len())).unwrap();
+        writeln!(writer, "{}", step.narrative).unwrap();
+        writeln!(writer).unwrap();
+        card.print_info(&mut writer);
+    }
+}
+
+/// How many steps of each journey have been completed, by journey name.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct JourneyProgress {
+    completed: HashMap<String, usize>,
+}
+
+impl SavedState for JourneyProgress {}
+
+impl JourneyProgress {
+    pub fn completed(&self, journey: &Journey) -> usize {
+        self.completed.get(&journey.name).copied().unwrap_or(0)
+    }
+
+    pub fn set_completed(&mut self, journey: &Journey, steps: usize) {
+        self.completed.insert(journey.name.clone(), steps);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::journey::{Journey, JourneyProgress};
+    use crate::deck::Deck;
+    use crate::deck::test_utils::utils::return_test_deck;
+    use crate::stored_element::{SavedState, StoredElement};
+
+    #[test]
+    fn bundled_journey() {
+        let journey = Journey::new_from_json(include_str!("../default_files/included_journeys/fools_journey.json"));
+        let deck = Deck::new_from_json(include_str!("../default_files/included_decks/default_deck.json"));
+        assert_eq!(journey.len(), 22);
+        let ranks: Vec<u8> = journey.steps.iter().map(|step| step.rank).collect();
+        assert_eq!(ranks, (0..22).collect::<Vec<u8>>());
+
+        let mut test_result = Vec::new();
+        journey.print_step(1, &deck, &mut test_result);
+        let output = String::from_utf8(test_result).unwrap();
+        assert!(output.starts_with("Stage 2 of 22\n=============\nThe first person the Fool meets"));
+        assert!(output.contains("\nThe Magician\n------------\n"));
+        assert!(output.contains("Keywords: "));
+        assert!(journey.missing_ranks(&deck).is_empty());
+    }
+
+    #[test]
+    fn journey_progress() {
+        let journey = Journey::new_from_json(r#"{
+            "name": "short",
+            "steps": [
+                { "rank": 1, "narrative": "second" },
+                { "rank": 0, "narrative": "first" }
+            ]
+        }"#);
+        assert_eq!(journey.steps[0].narrative, "first");
+        let deck = Deck::new_from_json(include_str!("../default_files/included_decks/default_deck.json"));
+        assert!(journey.missing_ranks(&deck).is_empty());
+        let deck = Deck::new_from_json(&return_test_deck());
+        assert_eq!(journey.missing_ranks(&deck), vec![0, 1]);
+
+        let mut progress = JourneyProgress::default();
+        assert_eq!(progress.completed(&journey), 0);
+        progress.set_completed(&journey, 1);
+
+        let mut path = std::env::temp_dir();
+        path.push(format!("terminal_tarot_journey_{}.json", std::process::id()));
+        progress.save(&path).unwrap();
+        assert_eq!(JourneyProgress::load(&path).unwrap().completed(&journey), 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -2,3 +2,19 @@ pub mod deck;
 pub mod spread;
 pub mod stored_element;
 pub mod default_files;
+pub mod journal;
+pub mod summary;
+pub mod dignities;
+pub mod combinations;
+pub mod template;
+pub mod daily;
+pub mod numerology;
+pub mod calendar;
+pub mod search;
+pub mod study;
+pub mod journey;
+pub mod oracle;
+pub mod timing;
+pub mod entropy;
+pub mod audit;
+pub mod probability;
@@ -0,0 +1,112 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::deck::Card;
+use crate::summary::SummaryNotes;
+
+/// The Fool is counted as 22 when sums are reduced, as there is no card 0 to land on.
+const FOOL_VALUE: u32 = 22;
+
+fn digit_sum(mut n: u32) -> u32 {
+    let mut sum = 0;
+    while n > 0 {
+        sum += n % 10;
+        n /= 10;
+    }
+    sum
+}
+
+fn to_rank(n: u32) -> u8 {
+    match n {
+        FOOL_VALUE => 0,
+        n => n as u8,
+    }
+}
+
+/// Sums digits until the total is a major arcana number, returning its rank.
+pub fn reduce(mut n: u32) -> u8 {
+    while n > FOOL_VALUE {
+        n = digit_sum(n);
+    }
+    to_rank(n)
+}
+
+/// Reduces a sum to a major arcana number, then keeps reducing to a single
+/// digit, giving the ranks of each card along the way.
+fn reduce_chain(n: u32) -> Vec<u8> {
+    let mut n = n;
+    while n > FOOL_VALUE {
+        n = digit_sum(n);
+    }
+    let mut chain = vec![ to_rank(n) ];
+    while n >= 10 {
+        n = digit_sum(n);
+        chain.push(to_rank(n));
+    }
+    chain
+}
+
+fn date_sum(month: u32, day: u32, year: i32) -> u32 {
+    month + day + year.unsigned_abs()
+}
+
+/// Ranks of the birth cards for a birth date: the reduced sum of month, day
+/// and year, followed by its own reductions down to a single digit.
+pub fn birth_cards(birth: NaiveDate) -> Vec<u8> {
+    reduce_chain(date_sum(birth.month(), birth.day(), birth.year()))
+}
+
+/// Rank of the personal year card, using the birth month and day with the given year.
+pub fn year_card(birth: NaiveDate, year: i32) -> u8 {
+    reduce(date_sum(birth.month(), birth.day(), year))
+}
+
+/// Rank of the major arcana card summarising a set of cards: the sum of
+/// their ranks, reduced. Court cards carry no number and are left out.
+pub fn quintessence<'c>(cards: impl IntoIterator<Item = &'c Card>, notes: &SummaryNotes) -> u8 {
+    reduce(cards.into_iter()
+        .filter(|c| c.is_major() || !notes.is_court(c.rank()))
+        .map(|c| c.rank() as u32)
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use crate::numerology::{birth_cards, quintessence, reduce, year_card};
+    use crate::deck::Deck;
+    use crate::deck::test_utils::utils::return_test_deck;
+    use crate::stored_element::StoredElement;
+    use crate::summary::SummaryNotes;
+
+    #[test]
+    fn reduce_sums() {
+        assert_eq!(reduce(7), 7);
+        assert_eq!(reduce(22), 0);
+        assert_eq!(reduce(2011), 4);
+        assert_eq!(reduce(1966), 0);
+    }
+
+    #[test]
+    fn birth_and_year_cards() {
+        // 7 + 14 + 1990 = 2011, reducing to The Emperor alone
+        assert_eq!(birth_cards(NaiveDate::from_ymd_opt(1990, 7, 14).unwrap()), vec![4]);
+        // 12 + 25 + 1980 = 2017 -> 10 -> 1: The Wheel and The Magician
+        assert_eq!(birth_cards(NaiveDate::from_ymd_opt(1980, 12, 25).unwrap()), vec![10, 1]);
+        // 5 + 14 + 1972 = 1991 -> 20 -> 2
+        assert_eq!(birth_cards(NaiveDate::from_ymd_opt(1972, 5, 14).unwrap()), vec![20, 2]);
+        // 1 + 1 + 2000 = 2002 -> 4, while 2 + 15 + 2003 = 2020 -> 4
+        assert_eq!(year_card(NaiveDate::from_ymd_opt(1985, 1, 1).unwrap(), 2000), 4);
+        assert_eq!(year_card(NaiveDate::from_ymd_opt(1985, 2, 15).unwrap(), 2003), 4);
+    }
+
+    #[test]
+    fn deck_quintessence() {
+        let deck = Deck::new_from_json(&return_test_deck());
+        let notes = SummaryNotes::default();
+        assert_eq!(quintessence(deck.draw(3), &notes), 3);
+
+        // test_name2 as a queen adds nothing, leaving test_name1's 1
+        let courts = Deck::new_from_json(&return_test_deck().replace(r#""rank": 2,"#, r#""rank": 13,"#));
+        assert_eq!(quintessence(courts.draw(3), &notes), 1);
+    }
+}
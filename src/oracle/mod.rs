@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::deck::{Card, Deck, MeaningHistory, MeaningStrategy, ShuffleOptions};
+use crate::journal::{PositionRecord, ReadingRecord};
+
+pub const ORACLE_SPREAD: &str = "Yes or No";
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Answer {
+    Yes,
+    No,
+    Unclear,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Yes => write!(f, "Yes"),
+            Answer::No => write!(f, "No"),
+            Answer::Unclear => write!(f, "Unclear"),
+        }
+    }
+}
+
+/// A yes or no question answered by the summed polarity of the cards drawn for it.
+pub struct OracleReading<'a> {
+    question: String,
+    jumpers: &'a [Card],
+    cards: &'a [Card],
+}
+
+impl<'a> OracleReading<'a> {
+    pub fn new(question: &str, deck: &'a mut Deck, count: usize, seed: u64, shuffle: &ShuffleOptions) -> OracleReading<'a> {
+        let jumper_count = deck.shuffle_to_deal(seed, shuffle, count);
+        let deck: &'a Deck = deck;
+        let (jumpers, cards) = deck.draw(jumper_count + count).split_at(jumper_count);
+        OracleReading {
+            question: question.to_string(),
+            jumpers,
+            cards,
+        }
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        self.cards
+    }
+
+    /// Cards that flew out of the deck while shuffling, which don't count towards the answer.
+    pub fn jumpers(&self) -> &[Card] {
+        self.jumpers
+    }
+
+    pub fn score(&self) -> i32 {
+        self.cards.iter().map(|card| card.polarity()).sum()
+    }
+
+    pub fn answer(&self) -> Answer {
+        match self.score() {
+            score if score > 0 => Answer::Yes,
+            score if score < 0 => Answer::No,
+            _ => Answer::Unclear,
+        }
+    }
+
+    pub fn print(&self, seed: u64, mut writer: impl std::io::Write) {
+        writeln!(writer, "{}", self.question).unwrap();
+        writeln!(writer, "{}", "-".repeat(self.question.chars().count())).unwrap();
+        if !self.jumpers.is_empty() {
+            writeln!(writer).unwrap();
+            writeln!(writer, "Jumpers\n-------\nCards that flew out of the deck while shuffling").unwrap();
+            for card in self.jumpers {
+                writeln!(writer).unwrap();
+                card.print(seed, &mut writer);
+            }
+            writeln!(writer).unwrap();
+            writeln!(writer, "{}", "=".repeat(30)).unwrap();
+            writeln!(writer).unwrap();
+        }
+        writeln!(writer, "Answer: {} ({:+})", self.answer(), self.score()).unwrap();
+        for card in self.cards {
+            writeln!(writer).unwrap();
+            card.print(seed, &mut writer);
+            let lean = match card.polarity() {
+                p if p > 0 => "Leans yes",
+                p if p < 0 => "Leans no",
+                _ => "Undecided",
+            };
+            writeln!(writer, "{} ({:+})", lean, card.polarity()).unwrap();
+        }
+    }
+
+    pub fn to_record(&self, deck_name: &str, seed: u64, timestamp: u64) -> ReadingRecord {
+        ReadingRecord {
+            timestamp,
+            seed,
+            deck: deck_name.to_string(),
+            spread: ORACLE_SPREAD.to_string(),
+            positions: self.cards.iter().enumerate()
+                .map(|(i, card)| {
+                    let model = card.to_model(seed, None, MeaningStrategy::Random, &MeaningHistory::default());
+                    PositionRecord {
+                        position: format!("Card {}", i + 1),
+                        card: model.name,
+                        reversed: model.reversed,
+                        shown_meanings: vec![ model.fortune, model.light, model.shadow ],
+                    }
+                })
+                .collect(),
+            jumpers: self.jumpers.iter().map(|card| card.name().to_string()).collect(),
+            question: Some(self.question.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::oracle::{Answer, OracleReading};
+    use crate::deck::{Deck, ShuffleOptions};
+    use crate::deck::test_utils::utils::return_test_deck;
+    use crate::stored_element::StoredElement;
+
+    fn polarity_deck() -> String {
+        return_test_deck()
+            .replace(r#""suit": "test_suit","#, r#""suit": "swords","#)
+            .replace(r#""name": "test_name2","#, r#""name": "test_name2", "polarity": 3,"#)
+    }
+
+    #[test]
+    fn oracle_answers() {
+        let mut deck = Deck::new_from_json(&polarity_deck());
+        let cards: Vec<i32> = deck.cards().iter().map(|card| card.polarity()).collect();
+        assert_eq!(cards, vec![-1, 1, 3]);
+
+        let reading = OracleReading::new("Will it work?", &mut deck, 3, 1, &ShuffleOptions::default());
+        assert_eq!((reading.score(), reading.answer()), (3, Answer::Yes));
+
+        let mut deck = Deck::new_from_json(&polarity_deck());
+        let reversed = ShuffleOptions { reversal_chance: 1.0, ..ShuffleOptions::default() };
+        let reading = OracleReading::new("Will it work?", &mut deck, 3, 1, &reversed);
+        assert_eq!((reading.score(), reading.answer()), (-3, Answer::No));
+
+        let mut deck = Deck::new_from_json(&polarity_deck());
+        let reading = OracleReading::new("Will it work?", &mut deck, 1, 1, &ShuffleOptions::default());
+        assert_eq!(reading.cards()[0].name(), "test_name1");
+
+        let record = reading.to_record("test deck", 1, 0);
+        assert_eq!(record.question.as_deref(), Some("Will it work?"));
+        assert_eq!(record.positions[0].position, "Card 1");
+
+        let mut test_result = Vec::new();
+        reading.print(1, &mut test_result);
+        let output = String::from_utf8(test_result).unwrap();
+        assert!(output.starts_with("Will it work?\n-------------\nAnswer: Yes (+1)\n\ntest_name1\n"));
+        assert!(output.ends_with("Leans yes (+1)\n"));
+
+        let mut deck = Deck::new_from_json(&polarity_deck());
+        let reading = OracleReading::new("¿Sí?", &mut deck, 1, 1, &ShuffleOptions::default());
+        let mut test_result = Vec::new();
+        reading.print(1, &mut test_result);
+        assert!(String::from_utf8(test_result).unwrap().starts_with("¿Sí?\n----\n"));
+    }
+
+    #[test]
+    fn oracle_jumpers() {
+        let mut deck = Deck::new_from_json(&polarity_deck());
+        let jumping = ShuffleOptions { jumper_chance: 1.0, ..ShuffleOptions::default() };
+        let reading = OracleReading::new("Will it work?", &mut deck, 1, 1, &jumping);
+        assert_eq!((reading.jumpers().len(), reading.cards().len()), (2, 1));
+        assert!(reading.jumpers().iter().all(|jumper| jumper.name() != reading.cards()[0].name()));
+        assert_eq!(reading.score(), reading.cards()[0].polarity());
+
+        let record = reading.to_record("test deck", 1, 0);
+        assert_eq!(record.jumpers.len(), 2);
+        assert_eq!(record.positions.len(), 1);
+
+        let mut test_result = Vec::new();
+        reading.print(1, &mut test_result);
+        let output = String::from_utf8(test_result).unwrap();
+        assert!(output.starts_with("Will it work?\n-------------\n\nJumpers\n-------\n"));
+        assert!(output.contains("==============================\n\nAnswer: "));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::deck::{Card, Deck, ShuffleOptions};
+
+/// What has to happen to the card for the query to succeed.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Event {
+    /// Anywhere in the spread.
+    Appears,
+    /// In the position at this index.
+    InPosition(usize),
+    /// Anywhere in the spread along with the named card.
+    With(String),
+}
+
+/// A question about one card's chance of turning up in a spread.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Query {
+    pub card: String,
+    pub event: Event,
+    /// Whether every named card has to be drawn reversed.
+    pub reversed: bool,
+}
+
+impl Query {
+    /// The query in words, naming positions from `positions`.
+    pub fn describe(&self, positions: &[&str]) -> String {
+        let reversed = match self.reversed {
+            true => " reversed",
+            false => "",
+        };
+        match &self.event {
+            Event::Appears => format!("{} appears{}", self.card, reversed),
+            Event::InPosition(index) => format!("{} appears{} in position {} ({})", self.card, reversed, index + 1, positions[*index]),
+            Event::With(other) => format!("{} and {} both appear{}", self.card, other, reversed),
+        }
+    }
+
+    fn cards(&self) -> usize {
+        match self.event {
+            Event::With(_) => 2,
+            _ => 1,
+        }
+    }
+
+    fn matches(&self, drawn: &[Card]) -> bool {
+        let found = |name: &str| drawn.iter()
+            .position(|card| card.name().eq_ignore_ascii_case(name) && (!self.reversed || card.is_reversed()));
+        match &self.event {
+            Event::Appears => found(&self.card).is_some(),
+            Event::InPosition(index) => found(&self.card) == Some(*index),
+            Event::With(other) => found(&self.card).is_some() && found(other).is_some(),
+        }
+    }
+}
+
+/// Exact chance for a uniformly shuffled deck of `deck_size` cards dealt into
+/// `positions` positions, each card reversed with `reversal_chance`.
+pub fn exact(deck_size: usize, positions: usize, reversal_chance: f64, query: &Query) -> f64 {
+    let n = deck_size as f64;
+    let k = positions.min(deck_size) as f64;
+    let placed = match query.event {
+        Event::Appears => k / n,
+        Event::InPosition(index) if index < positions && index < deck_size => 1.0 / n,
+        Event::InPosition(_) => 0.0,
+        Event::With(_) => k * (k - 1.0) / (n * (n - 1.0)),
+    };
+    match query.reversed {
+        true => placed * reversal_chance.powi(query.cards() as i32),
+        false => placed,
+    }
+}
+
+/// Share of `runs` shuffles, with seeds counting up from `seed`, where the
+/// query succeeds. Jumpers are set aside before the spread is dealt.
+pub fn simulate(deck: &Deck, positions: usize, query: &Query, runs: usize, seed: u64, options: &ShuffleOptions) -> f64 {
+    let hits = (0..runs)
+        .filter(|run| {
+            let mut shuffled = deck.clone();
+            let jumpers = shuffled.shuffle_to_deal(seed.wrapping_add(*run as u64), options, positions);
+            query.matches(&shuffled.draw(jumpers + positions)[jumpers..])
+        })
+        .count();
+    hits as f64 / runs as f64
+}
+
+/// A chance as a percentage and as "1 in n".
+pub fn describe_chance(chance: f64) -> String {
+    match chance > 0.0 {
+        true => format!("{:.4}% (1 in {:.1})", chance * 100.0, 1.0 / chance),
+        false => String::from("0% (impossible)"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::probability::{describe_chance, exact, simulate, Event, Query};
+    use crate::deck::{Deck, ShuffleOptions};
+    use crate::deck::test_utils::utils::return_test_deck;
+    use crate::stored_element::StoredElement;
+
+    fn query(event: Event, reversed: bool) -> Query {
+        Query { card: String::from("test_name1"), event, reversed }
+    }
+
+    #[test]
+    fn exact_chances() {
+        assert!((exact(78, 3, 0.0, &query(Event::Appears, false)) - 3.0 / 78.0).abs() < 1e-12);
+        assert!((exact(78, 3, 0.0, &query(Event::InPosition(2), false)) - 1.0 / 78.0).abs() < 1e-12);
+        assert_eq!(exact(78, 3, 0.0, &query(Event::InPosition(3), false)), 0.0);
+        let together = query(Event::With(String::from("test_name2")), true);
+        assert!((exact(78, 10, 0.5, &together) - 90.0 / (78.0 * 77.0) * 0.25).abs() < 1e-12);
+        assert_eq!(exact(3, 3, 0.0, &query(Event::Appears, false)), 1.0);
+
+        assert_eq!(describe_chance(0.25), "25.0000% (1 in 4.0)");
+        assert_eq!(query(Event::InPosition(1), true).describe(&["past", "future"]), "test_name1 appears reversed in position 2 (future)");
+    }
+
+    #[test]
+    fn simulated_chances() {
+        let deck = Deck::new_from_json(&return_test_deck());
+        let options = ShuffleOptions { reversal_chance: 0.5, ..ShuffleOptions::default() };
+        for query in [
+            query(Event::Appears, false),
+            query(Event::InPosition(0), true),
+            query(Event::With(String::from("test_name2")), false),
+        ] {
+            let simulated = simulate(&deck, 2, &query, 4000, 1, &options);
+            assert!((simulated - exact(3, 2, 0.5, &query)).abs() < 0.03, "{:?} {}", query, simulated);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use crate::deck::{Card, Deck};
+
+/// The part of a card a search matched.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Field {
+    Keyword,
+    Fortune,
+    Light,
+    Shadow,
+}
+
+impl Field {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Field::Keyword => "Keyword",
+            Field::Fortune => "Fortune",
+            Field::Light => "Light",
+            Field::Shadow => "Shadow",
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct SearchHit<'a> {
+    pub deck: &'a Deck,
+    pub card: &'a Card,
+    pub field: Field,
+    pub text: &'a str,
+}
+
+/// Every keyword, fortune and meaning containing `term`, ignoring case.
+pub fn search<'a>(decks: &'a [Deck], term: &str) -> Vec<SearchHit<'a>> {
+    let term = term.to_lowercase();
+    let mut hits = Vec::new();
+    for deck in decks {
+        for card in deck.cards() {
+            let fields = [
+                (Field::Keyword, card.keywords()),
+                (Field::Fortune, card.fortunes()),
+                (Field::Light, card.lights()),
+                (Field::Shadow, card.shadows()),
+            ];
+            for (field, texts) in fields.iter() {
+                for text in texts.iter().filter(|t| t.to_lowercase().contains(&term)) {
+                    hits.push(SearchHit { deck, card, field: *field, text });
+                }
+            }
+        }
+    }
+    hits
+}
+
+/// Wraps each case-insensitive occurrence of `term` in `text` with the given markers.
+pub fn highlight(text: &str, term: &str, start: &str, end: &str) -> String {
+    let lower = text.to_lowercase();
+    let term = term.to_lowercase();
+    if term.is_empty() || lower.len() != text.len() {
+        return text.to_string();
+    }
+    let mut highlighted = String::new();
+    let mut last = 0;
+    for (i, _) in lower.match_indices(&term) {
+        highlighted.push_str(&text[last..i]);
+        highlighted.push_str(start);
+        highlighted.push_str(&text[i..i + term.len()]);
+        highlighted.push_str(end);
+        last = i + term.len();
+    }
+    highlighted.push_str(&text[last..]);
+    highlighted
+}
+
+/// Prints hits under their deck and card names with the term highlighted.
+pub fn print_hits(hits: &[SearchHit], term: &str, (start, end): (&str, &str), mut writer: impl std::io::Write) {
+    let mut last: Option<(&Deck, &Card)> = None;
+    for hit in hits {
+        if !matches!(last, Some((deck, _)) if std::ptr::eq(deck, hit.deck)) {
+            if last.is_some() {
+                writeln!(writer).unwrap();
+            }
+            writeln!(writer, "{}", hit.deck.name).unwrap();
+            writeln!(writer, "{}", "=".repeat(hit.deck.name.len())).unwrap();
+        }
+        if !matches!(last, Some((_, card)) if std::ptr::eq(card, hit.card)) {
+            writeln!(writer, "{}", hit.card.name()).unwrap();
+        }
+        writeln!(writer, "  {}: {}", hit.field.label(), highlight(hit.text, term, start, end)).unwrap();
+        last = Some((hit.deck, hit.card));
+    }
+}
+
+/// Which cards carry each keyword, with keywords lowercased and sorted.
+pub fn keyword_index(decks: &[Deck]) -> BTreeMap<String, Vec<&Card>> {
+    let mut index: BTreeMap<String, Vec<&Card>> = BTreeMap::new();
+    for card in decks.iter().flat_map(|deck| deck.cards()) {
+        for keyword in card.keywords() {
+            index.entry(keyword.to_lowercase()).or_default().push(card);
+        }
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search::{highlight, keyword_index, search, Field};
+    use crate::deck::Deck;
+    use crate::deck::test_utils::utils::return_test_deck;
+    use crate::stored_element::StoredElement;
+
+    #[test]
+    fn search_decks() {
+        let decks = vec![ Deck::new_from_json(&return_test_deck()) ];
+        let hits = search(&decks, "FORTUNE2");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].card.name(), "test_name2");
+        assert_eq!(hits[0].field, Field::Fortune);
+        assert_eq!(hits[0].text, "test_fortune2");
+        assert_eq!(search(&decks, "shadow_meaning2").len(), 2);
+        assert_eq!(search(&decks, "keyword1").iter().map(|hit| hit.field).collect::<Vec<Field>>(), vec![Field::Keyword; 2]);
+        assert!(search(&decks, "nothing like this").is_empty());
+
+        assert_eq!(highlight("Take it on Faith, faith alone", "faith", "[", "]"), "Take it on [Faith], [faith] alone");
+    }
+
+    #[test]
+    fn keyword_reverse_index() {
+        let decks = vec![ Deck::new_from_json(&return_test_deck()) ];
+        let index = keyword_index(&decks);
+        let names: Vec<&str> = index["test_keyword1"].iter().map(|card| card.name()).collect();
+        assert_eq!(names, vec!["test_name", "test_name1"]);
+        assert_eq!(index.len(), 3);
+    }
+}
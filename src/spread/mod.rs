@@ -1,6 +1,26 @@
 use serde::Deserialize;
-use crate::deck::{Card, Deck};
+use crate::deck::{Card, Deck, MeaningHistory, MeaningStrategy, PositionCategory, ShuffleOptions};
 use crate::stored_element::StoredElement;
+use crate::journal::{PositionRecord, ReadingRecord};
+use crate::summary::{ReadingSummary, SummaryNotes};
+use crate::dignities::{Dignity, Element};
+use crate::combinations::Combination;
+use crate::numerology;
+use crate::timing;
+use crate::template::{
+    DignityModel,
+    NeighbourModel,
+    PlacementModel,
+    PositionModel,
+    ReadingModel,
+    ReadingTemplate,
+    RemainingModel,
+    SuitCountModel,
+    TimingEntryModel,
+    TimingModel,
+    PAUSE_MARKER
+};
+use std::path::PathBuf;
 
 #[derive(Deserialize, PartialEq, Debug)]
 enum PosOrientation {
@@ -8,12 +28,56 @@ enum PosOrientation {
     Vertical,
 }
 
+#[derive(Deserialize, PartialEq, Debug)]
+struct Narrative {
+    #[serde(default)]
+    intro: String,
+    position_template: String,
+    #[serde(default)]
+    outro: String,
+}
+
+const DEFAULT_POSITION_TEMPLATE: &str = "In {position}, {card} appears. {fortune}, with {keyword} at its heart.";
+
+/// Replaces each `{placeholder}` in the template, dropping a full stop that
+/// directly follows a value which already ends in punctuation.
+fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut filled = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        let found = placeholder.find('}').and_then(|end| values.iter()
+            .find(|(name, _)| *name == &placeholder[1..end])
+            .map(|(_, value)| (end, *value)));
+        match found {
+            Some((end, value)) => {
+                filled.push_str(value);
+                rest = &placeholder[end + 1..];
+                if value.ends_with(&['.', '?', '!'][..]) && rest.starts_with('.') {
+                    rest = &rest[1..];
+                }
+            },
+            None => {
+                filled.push('{');
+                rest = &placeholder[1..];
+            },
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
 #[derive(Deserialize, PartialEq, Debug)]
 pub struct Spread {
     positions: Vec<Position>,
     position_x_size: u8,
     position_y_size: u8,
-    name: String 
+    name: String,
+    #[serde(default)]
+    narrative: Option<Narrative>,
+    #[serde(default)]
+    meaning_strategy: Option<MeaningStrategy>,
 }
 
 use std::fmt;
@@ -28,40 +92,325 @@ impl StoredElement for Spread {
         serde_json::from_str(json).expect("Failed to deserialize spread file")
     }
 }
+
+impl Spread {
+    /// Position names in the order cards are dealt into them.
+    pub fn position_names(&self) -> Vec<&str> {
+        self.positions.iter().map(|pos| pos.name.as_str()).collect()
+    }
+
+    /// Whether the positions at these indices are neighbours. Spreads that list
+    /// `neighbours` for their positions are taken at their word, either side
+    /// listing the other being enough. Otherwise positions are neighbours when
+    /// they share a spot or sit directly beside, above or below each other.
+    fn are_neighbours(&self, a: usize, b: usize) -> bool {
+        let (a, b) = (&self.positions[a], &self.positions[b]);
+        match self.positions.iter().any(|pos| pos.neighbours.is_some()) {
+            true => [(a, b), (b, a)].iter().any(|(pos, other)| {
+                pos.neighbours.as_ref().is_some_and(|orders| orders.contains(&other.order))
+            }),
+            false => {
+                let x_distance = (i16::from(a.x_pos) - i16::from(b.x_pos)).abs();
+                let y_distance = (i16::from(a.y_pos) - i16::from(b.y_pos)).abs();
+                x_distance + y_distance <= 1
+            },
+        }
+    }
+}
     
 
 #[derive(PartialEq, Debug)]
 pub struct FilledSpread<'a> {
     spread: Spread,
+    jumpers: &'a [Card],
     cards: &'a [Card],
+    remaining: &'a [Card],
+    notes: &'a SummaryNotes,
+    combinations: &'a [Combination],
+}
+
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ReadingOptions {
+    pub shadow_card: bool,
+    pub remaining_summary: bool,
+    pub summary: bool,
+    pub dignities: bool,
+    pub narrative: bool,
+    pub correspondences: bool,
+    pub timing: bool,
+    pub question: Option<String>,
+    pub template: Option<PathBuf>,
+    pub meaning_strategy: Option<MeaningStrategy>,
+    pub meaning_history: MeaningHistory,
+}
+
+fn wait_for_enter(mut writer: impl std::io::Write) {
+    writeln!(writer, "Press ENTER to draw next card").unwrap();
+    let mut press = String::new();
+    std::io::stdin().read_line(&mut press).unwrap();
 }
 
 impl<'a> FilledSpread<'a> {
 
-    pub fn new(spread: Spread, deck: &mut Deck, seed: u64) -> FilledSpread {
-        deck.shuffle_deck(seed);
-        let pos_count = spread.positions.len();
+    pub fn new(spread: Spread, deck: &mut Deck, seed: u64) -> FilledSpread<'_> {
+        FilledSpread::new_with_shuffle(spread, deck, seed, &ShuffleOptions::default())
+    }
+
+    pub fn new_with_shuffle(spread: Spread, deck: &'a mut Deck, seed: u64, shuffle: &ShuffleOptions) -> FilledSpread<'a> {
+        let jumper_count = deck.shuffle_to_deal(seed, shuffle, spread.positions.len());
+        let deck: &'a Deck = deck;
+        let drawn_count = jumper_count + spread.positions.len();
+        let (jumpers, cards) = deck.draw(drawn_count).split_at(jumper_count);
         FilledSpread {
-            spread: spread,
-            cards: deck.draw(pos_count),
+            spread,
+            jumpers,
+            cards,
+            remaining: deck.remaining(drawn_count),
+            notes: deck.summary_notes(),
+            combinations: deck.combinations(),
+        }
+    }
+
+    /// Rank of the major arcana card summing up the cards in the spread.
+    pub fn quintessence(&self) -> u8 {
+        numerology::quintessence(self.cards, self.notes)
+    }
+
+    /// The card left at the bottom of the deck after shuffling, if any remain.
+    pub fn shadow_card(&self) -> Option<&Card> {
+        self.remaining.last()
+    }
+
+    pub fn to_record(&self, deck_name: &str, seed: u64, timestamp: u64, options: &ReadingOptions) -> ReadingRecord {
+        let model = self.to_model(seed, options);
+        ReadingRecord {
+            timestamp,
+            seed,
+            deck: deck_name.to_string(),
+            spread: self.spread.name.clone(),
+            positions: model.positions.into_iter()
+                .map(|placement| PositionRecord {
+                    position: placement.position.name,
+                    card: placement.card.name,
+                    reversed: placement.card.reversed,
+                    shown_meanings: placement.card.fortunes.into_iter()
+                        .chain(placement.card.lights)
+                        .chain(placement.card.shadows)
+                        .collect(),
+                })
+                .collect(),
+            jumpers: self.jumpers.iter().map(|card| card.name().to_string()).collect(),
+            question: options.question.clone(),
+        }
+    }
+
+    /// The explicitly requested meaning strategy, then the spread's own, then random.
+    pub fn meaning_strategy(&self, options: &ReadingOptions) -> MeaningStrategy {
+        options.meaning_strategy
+            .or(self.spread.meaning_strategy)
+            .unwrap_or(MeaningStrategy::Random)
+    }
+
+    pub fn print(&self, iflag: bool, seed: u64, writer: impl std::io::Write) {
+        self.print_with(iflag, seed, &ReadingOptions::default(), writer);
+    }
+
+    pub fn print_with(&self, iflag: bool, seed: u64, options: &ReadingOptions, mut writer: impl std::io::Write) {
+        let template = match &options.template {
+            Some(path) => ReadingTemplate::new_from_path(path),
+            None => ReadingTemplate::default(),
+        };
+        let rendered = template.render("reading", &self.to_model(seed, options));
+        let mut sections = rendered.split(PAUSE_MARKER).peekable();
+        while let Some(section) = sections.next() {
+            write!(&mut writer, "{}", section).unwrap();
+            if iflag && sections.peek().is_some() {
+                wait_for_enter(&mut writer);
+            }
+        }
+    }
+
+    /// The reading as handed to templates, with meanings chosen for this seed
+    /// and the optional sections filled in as requested.
+    pub fn to_model(&self, seed: u64, options: &ReadingOptions) -> ReadingModel {
+        let narrative = self.spread.narrative.as_ref();
+        let position_template = narrative.map_or(DEFAULT_POSITION_TEMPLATE, |n| n.position_template.as_str());
+        let strategy = self.meaning_strategy(options);
+        let history = &options.meaning_history;
+        let card_model = |card: &Card, category| {
+            let mut model = card.to_model(seed, category, strategy, history);
+            if options.correspondences {
+                model.correspondences = Some(card.correspondences().clone());
+            }
+            model
+        };
+        let positions = self.spread.positions.iter().zip(self.cards.iter())
+            .map(|(pos, card)| {
+                let card = card_model(card, pos.category);
+                let template = pos.narrative.as_deref().unwrap_or(position_template);
+                PlacementModel {
+                    position: pos.to_model(),
+                    narrative: fill_template(template, &[
+                        ("position", &pos.name),
+                        ("meaning", &pos.meaning),
+                        ("card", &card.title),
+                        ("keyword", &card.keyword),
+                        ("fortune", &card.fortune),
+                    ]),
+                    card,
+                }
+            })
+            .collect();
+
+        ReadingModel {
+            question: options.question.clone(),
+            spread: self.spread.name.clone(),
+            seed,
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            jumpers: self.jumpers.iter().map(|card| card_model(card, None)).collect(),
+            positions,
+            narrative: options.narrative,
+            narrative_intro: narrative.map_or(String::new(), |n| n.intro.clone()),
+            narrative_outro: narrative.map_or(String::new(), |n| n.outro.clone()),
+            combinations: self.found_combinations().iter().map(|combination| combination.to_model()).collect(),
+            dignities: match options.dignities {
+                true => Some(self.dignities_model()),
+                false => None,
+            },
+            summary: match options.summary {
+                true => Some(ReadingSummary::new(self.cards, self.notes).to_model(self.notes)),
+                false => None,
+            },
+            timing: match options.timing {
+                true => Some(self.timing_model()),
+                false => None,
+            },
+            shadow_card: match options.shadow_card {
+                true => self.shadow_card().map(|card| card_model(card, None)),
+                false => None,
+            },
+            remaining: match options.remaining_summary {
+                true => Some(self.remaining_model()),
+                false => None,
+            },
+        }
+    }
+
+    fn timing_model(&self) -> TimingModel {
+        let estimates = timing::estimate(self.cards, self.notes);
+        let entries = self.spread.positions.iter().zip(self.cards.iter())
+            .filter_map(|(pos, card)| estimates.iter()
+                .find(|estimate| std::ptr::eq(estimate.card, card))
+                .map(|estimate| TimingEntryModel {
+                    position: pos.name.clone(),
+                    card: card.title(),
+                    estimate: estimate.describe(),
+                    days: estimate.days(),
+                }))
+            .collect();
+        let range = timing::range(&estimates);
+        TimingModel {
+            entries,
+            soonest: range.map(|(soonest, _)| soonest.describe()),
+            latest: range
+                .filter(|(soonest, latest)| soonest.days() != latest.days())
+                .map(|(_, latest)| latest.describe()),
         }
     }
 
-    pub fn print(&self, iflag: bool, seed: u64, mut writer: impl std::io::Write) {
-        let filled_pos = self.spread.positions.iter().zip(self.cards.iter());
-        for pos in filled_pos {
-            pos.0.print(&mut writer);
-            writeln!(&mut writer, "").unwrap();
-            pos.1.print(seed, &mut writer);
-            writeln!(&mut writer, "").unwrap();
-            writeln!(&mut writer, "{}", std::iter::repeat("=").take(30).collect::<String>()).unwrap();
-            writeln!(&mut writer, "").unwrap();
-            if iflag {
-                writeln!(&mut writer, "Press ENTER to draw next card").unwrap();
-                let mut press = String::new();
-                std::io::stdin().read_line(&mut press).unwrap();
+    /// Combinations from the deck whose cards all appear in the spread, sitting
+    /// in a connected group of neighbouring positions when the combination asks for it.
+    pub fn found_combinations(&self) -> Vec<&'a Combination> {
+        let combinations: &'a [Combination] = self.combinations;
+        combinations.iter()
+            .filter(|combination| {
+                let indices = combination.cards().iter()
+                    .map(|name| self.cards.iter().position(|card| card.name().eq_ignore_ascii_case(name)))
+                    .collect::<Option<Vec<usize>>>();
+                match indices {
+                    None => false,
+                    Some(indices) => !combination.adjacent() || self.positions_connected(&indices),
+                }
+            })
+            .collect()
+    }
+
+    fn positions_connected(&self, indices: &[usize]) -> bool {
+        let mut reached = vec![ indices[0] ];
+        let mut frontier = vec![ indices[0] ];
+        while let Some(current) = frontier.pop() {
+            for index in indices {
+                if !reached.contains(index)
+                    && self.spread.are_neighbours(current, *index) {
+                    reached.push(*index);
+                    frontier.push(*index);
+                }
             }
         }
+        indices.iter().all(|index| reached.contains(index))
+    }
+
+    /// The card's own element when the deck gives one, as trumps do, or else its suit's.
+    fn card_element(&self, card: &Card) -> Option<Element> {
+        card.correspondences().element.as_deref()
+            .or_else(|| self.notes.element(card.suit()))
+            .and_then(|element| element.parse().ok())
+    }
+
+    fn dignities_model(&self) -> Vec<DignityModel> {
+        let filled_pos = self.spread.positions.iter().zip(self.cards.iter()).collect::<Vec<(&Position, &Card)>>();
+        filled_pos.iter().enumerate()
+            .map(|(index, (pos, card))| {
+                let element = self.card_element(card);
+                let neighbours = filled_pos.iter().enumerate()
+                    .filter(|(other, _)| *other != index && self.spread.are_neighbours(index, *other))
+                    .map(|(_, (neighbour_pos, neighbour))| {
+                        let neighbour_element = self.card_element(neighbour);
+                        NeighbourModel {
+                            position: neighbour_pos.name.clone(),
+                            card: neighbour.title(),
+                            element: neighbour_element,
+                            dignity: match (element, neighbour_element) {
+                                (Some(own), Some(other)) => Dignity::between(own, other),
+                                _ => Dignity::Neutral,
+                            },
+                        }
+                    })
+                    .collect::<Vec<NeighbourModel>>();
+                DignityModel {
+                    position: pos.name.clone(),
+                    card: card.title(),
+                    element,
+                    dignity: Dignity::overall(&neighbours.iter().map(|n| n.dignity).collect::<Vec<Dignity>>()),
+                    neighbours,
+                }
+            })
+            .collect()
+    }
+
+    fn remaining_model(&self) -> RemainingModel {
+        use std::collections::BTreeMap;
+
+        let mut undrawn_majors = self.remaining.iter()
+            .filter(|card| card.is_major())
+            .collect::<Vec<&Card>>();
+        undrawn_majors.sort_by_key(|card| card.rank());
+
+        let mut suit_counts = BTreeMap::new();
+        for card in self.remaining.iter().filter(|card| !card.is_major()) {
+            *suit_counts.entry(card.suit()).or_insert(0) += 1;
+        }
+        RemainingModel {
+            count: self.remaining.len(),
+            majors: undrawn_majors.iter().map(|card| card.name().to_string()).collect(),
+            suits: suit_counts.into_iter()
+                .map(|(suit, count)| SuitCountModel {
+                    suit: suit.to_string(),
+                    count,
+                    element: self.notes.element(suit).map(String::from),
+                })
+                .collect(),
+        }
     }
 
 }
@@ -74,14 +423,25 @@ struct Position {
     orientation: PosOrientation,
     x_pos: i8,
     y_pos: i8,
+    #[serde(default)]
+    narrative: Option<String>,
+    #[serde(default)]
+    category: Option<PositionCategory>,
+    /// Orders of the positions this one touches, when the grid alone would mislead.
+    #[serde(default)]
+    neighbours: Option<Vec<u8>>,
 }
 
 impl Position {
-    fn print(&self, mut writer: impl std::io::Write) {
-        writeln!(writer, "{}", self.name).unwrap();
-        writeln!(writer, "{}", std::iter::repeat("-").take(self.name.len()).collect::<String>()).unwrap();
-        writeln!(writer, "{}", self.meaning).unwrap();
+    fn to_model(&self) -> PositionModel {
+        PositionModel {
+            order: self.order,
+            name: self.name.clone(),
+            meaning: self.meaning.clone(),
+            category: self.category,
+        }
     }
+
 }
 
 mod test_utils;
@@ -92,14 +452,19 @@ mod tests {
         gen_test_spread_json
     };
     use crate::spread::{
+        fill_template,
         FilledSpread,
+        ReadingOptions,
         Spread
     };
-    use crate::deck::Deck;
+    use crate::deck::{Deck, MeaningStrategy, ShuffleOptions, MAJOR_SUIT};
     use crate::deck::test_utils::utils::{
         return_test_deck
     };
     use crate::stored_element::StoredElement;
+    use crate::template::ReadingTemplate;
+    use std::io::Write;
+    use std::path::PathBuf;
     #[test]
     fn position_print() {
         let mut test_result = Vec::new();
@@ -109,7 +474,8 @@ mod tests {
 ---------------
 test meaning 1
 "#;
-        test_spread.positions[0].print(&mut test_result);
+        let rendered = ReadingTemplate::default().render("position", &test_spread.positions[0].to_model());
+        write!(&mut test_result, "{}", rendered).unwrap();
         let test_output = String::from_utf8(test_result).unwrap();
         assert_eq!(test_output, target_output);
 
@@ -159,10 +525,496 @@ Shadow: shadow_meaning2
 
     }
 
+    #[test]
+    fn filled_spread_print_bundled_template_files() {
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let test_filled_spread = FilledSpread::new(
+            gen_test_spread(),
+            &mut test_deck,
+            1
+        );
+        let options = ReadingOptions {
+            template: Some(PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src/default_files/included_templates"))),
+            ..ReadingOptions::default()
+        };
+        let mut default_result = Vec::new();
+        let mut file_result = Vec::new();
+        test_filled_spread.print(false, 1, &mut default_result);
+        test_filled_spread.print_with(false, 1, &options, &mut file_result);
+        assert_eq!(default_result, file_result);
+    }
+
+    #[test]
+    fn filled_spread_print_user_template() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("terminal_tarot_reading_test_{}.hbs", std::process::id()));
+        std::fs::write(&path, "{{spread}} ({{seed}})\n{{#each positions}}{{order}}. {{name}}: {{card.title}}, {{card.light}}\n{{/each}}").unwrap();
+
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let test_filled_spread = FilledSpread::new(
+            gen_test_spread(),
+            &mut test_deck,
+            1
+        );
+        let options = ReadingOptions {
+            template: Some(path.clone()),
+            ..ReadingOptions::default()
+        };
+        let mut test_result = Vec::new();
+        test_filled_spread.print_with(false, 1, &options, &mut test_result);
+        std::fs::remove_file(&path).unwrap();
+
+        let target_output = r#"test spread (1)
+1. test position 1: test_name1, light_meaning1
+2. test position 2: test_name2, light_meaning2
+"#;
+        assert_eq!(String::from_utf8(test_result).unwrap(), target_output);
+    }
+
+    #[test]
+    fn filled_spread_jumpers() {
+        let mut test_result = Vec::new();
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let shuffle = ShuffleOptions {
+            jumper_chance: 1.0,
+            ..ShuffleOptions::default()
+        };
+        let mut one_card_spread = gen_test_spread();
+        one_card_spread.positions.truncate(0);
+        let test_filled_spread = FilledSpread::new_with_shuffle(
+            one_card_spread,
+            &mut test_deck,
+            1,
+            &shuffle
+        );
+        assert_eq!(test_filled_spread.jumpers.len(), 3);
+        assert_eq!(test_filled_spread.cards.len(), 0);
+
+        test_filled_spread.print(false, 1, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        assert!(test_output.starts_with("Jumpers\n-------\n"));
+
+        let record = test_filled_spread.to_record("test deck", 1, 0, &ReadingOptions::default());
+        assert_eq!(record.jumpers.len(), 3);
+        assert!(record.positions.is_empty());
+
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let test_filled_spread = FilledSpread::new_with_shuffle(gen_test_spread(), &mut test_deck, 1, &shuffle);
+        assert_eq!((test_filled_spread.jumpers.len(), test_filled_spread.cards.len()), (1, 2));
+    }
+
+    #[test]
+    fn filled_spread_record() {
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let test_filled_spread = FilledSpread::new(
+            gen_test_spread(),
+            &mut test_deck,
+            1
+        );
+        let record = test_filled_spread.to_record("test deck", 1, 10, &ReadingOptions::default());
+        assert_eq!(record.spread, "test spread");
+        assert_eq!(record.positions[0].position, "test position 1");
+        assert_eq!(record.positions[0].card, "test_name1");
+        assert_eq!(
+            record.positions[0].shown_meanings,
+            vec!["test_fortune1", "light_meaning1", "shadow_meaning1"]
+            );
+        assert!(record.jumpers.is_empty());
+        assert_eq!(record.question, None);
+    }
+
+    #[test]
+    fn filled_spread_question() {
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let test_filled_spread = FilledSpread::new(
+            gen_test_spread(),
+            &mut test_deck,
+            1
+        );
+        let options = ReadingOptions {
+            question: Some(String::from("Where next?")),
+            ..ReadingOptions::default()
+        };
+        let mut test_result = Vec::new();
+        test_filled_spread.print_with(false, 1, &options, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        assert!(test_output.starts_with("Where next?\n===========\n\ntest position 1\n"));
+
+        let record = test_filled_spread.to_record("test deck", 1, 10, &options);
+        assert_eq!(record.question.as_deref(), Some("Where next?"));
+
+        let options = ReadingOptions {
+            question: Some(String::from("Où aller ?")),
+            ..ReadingOptions::default()
+        };
+        let mut test_result = Vec::new();
+        test_filled_spread.print_with(false, 1, &options, &mut test_result);
+        assert!(String::from_utf8(test_result).unwrap().starts_with("Où aller ?\n==========\n"));
+    }
+
+    #[test]
+    fn filled_spread_meaning_strategy() {
+        let mut test_spread = gen_test_spread();
+        test_spread.meaning_strategy = Some(MeaningStrategy::All);
+        let deck_json = return_test_deck().replacen(r#""light_meaning1""#, r#""light_meaning1", "light_meaning1b""#, 1);
+        let mut test_deck = Deck::new_from_json(&deck_json);
+        let test_filled_spread = FilledSpread::new(
+            test_spread,
+            &mut test_deck,
+            1
+        );
+
+        let model = test_filled_spread.to_model(1, &ReadingOptions::default());
+        assert_eq!(model.positions[0].card.lights, vec!["light_meaning1", "light_meaning1b"]);
+
+        let options = ReadingOptions {
+            meaning_strategy: Some(MeaningStrategy::First),
+            ..ReadingOptions::default()
+        };
+        let model = test_filled_spread.to_model(1, &options);
+        assert_eq!(model.positions[0].card.lights, vec!["light_meaning1"]);
+
+        let mut test_result = Vec::new();
+        test_filled_spread.print(false, 1, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        assert!(test_output.contains("Light: light_meaning1\nLight: light_meaning1b\n"));
+    }
+
+    #[test]
+    fn filled_spread_shadow_card() {
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let mut ref_deck = Deck::new_from_json(&return_test_deck());
+        ref_deck.shuffle_deck(1);
+        let test_filled_spread = FilledSpread::new(
+            gen_test_spread(),
+            &mut test_deck,
+            1
+        );
+        assert_eq!(test_filled_spread.shadow_card(), ref_deck.remaining(2).last());
+
+        let mut test_result = Vec::new();
+        let options = ReadingOptions {
+            shadow_card: true,
+            remaining_summary: true,
+            ..ReadingOptions::default()
+        };
+        test_filled_spread.print_with(false, 1, &options, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        let target_tail =
+r#"Shadow Card
+-----------
+The card at the bottom of the deck, working beneath the reading
+
+test_name
+---------
+test_fortune1
+Light: light_meaning2
+Shadow: shadow_meaning
+
+==============================
+
+Remaining Deck
+--------------
+1 card left undrawn
+Major arcana not drawn: none
+Suits left: test_suit 1
+
+==============================
+
+"#;
+        assert!(test_output.ends_with(target_tail));
+    }
+
+    #[test]
+    fn filled_spread_quintessence() {
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let test_filled_spread = FilledSpread::new(gen_test_spread(), &mut test_deck, 1);
+        // test_name1 and test_name2 are dealt, ranks 1 and 2
+        assert_eq!(test_filled_spread.quintessence(), 3);
+
+        let mut test_deck = Deck::new_from_json(&return_test_deck().replace(r#""rank": 2,"#, r#""rank": 12,"#));
+        let test_filled_spread = FilledSpread::new(gen_test_spread(), &mut test_deck, 1);
+        assert_eq!(test_filled_spread.quintessence(), 1);
+    }
+
+    #[test]
+    fn filled_spread_summary() {
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let test_filled_spread = FilledSpread::new(
+            gen_test_spread(),
+            &mut test_deck,
+            1
+        );
+        let mut test_result = Vec::new();
+        let options = ReadingOptions {
+            summary: true,
+            ..ReadingOptions::default()
+        };
+        test_filled_spread.print_with(false, 1, &options, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        let target_tail =
+r#"Summary
+-------
+Overview of the 2 cards in the spread
+
+Major arcana: 0, Minor arcana: 2
+Suits: test_suit1 1, test_suit2 1
+Court cards: 0
+Reversed cards: 0
+* Majority of minor arcana: the matter rests in everyday affairs and your own hands
+
+==============================
+
+"#;
+        assert!(test_output.ends_with(target_tail));
+    }
+
+    #[test]
+    fn filled_spread_timing() {
+        let mut test_deck = Deck::new_from_json(&return_test_deck().replace(
+            r#""name": "test deck","#,
+            r#""name": "test deck", "summary": { "timing_units": {
+                "test_suit": { "singular": "year", "plural": "years", "days": 365 },
+                "test_suit1": { "singular": "week", "plural": "weeks", "days": 7 },
+                "test_suit2": { "singular": "day", "plural": "days", "days": 1 }
+            } },"#
+            ).replacen(r#""rank": 0,"#, r#""rank": 14,"#, 1));
+        // test_name is dealt third as a court card, which gives no timing
+        let mut test_spread = gen_test_spread();
+        test_spread.positions.push(serde_json::from_str(r#"{
+            "order": 3, "name": "test position 3", "meaning": "test meaning 3",
+            "orientation": "Vertical", "x_pos": 5, "y_pos": 0
+        }"#).unwrap());
+        let test_filled_spread = FilledSpread::new(
+            test_spread,
+            &mut test_deck,
+            1
+        );
+        assert_eq!(test_filled_spread.cards[2].name(), "test_name");
+        let mut test_result = Vec::new();
+        let options = ReadingOptions {
+            timing: true,
+            ..ReadingOptions::default()
+        };
+        test_filled_spread.print_with(false, 1, &options, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        let target_tail =
+r#"Timing
+------
+When the numbered cards say the matter will come about
+
+test position 1: test_name1 suggests 1 week
+test position 2: test_name2 suggests 2 days
+Expect it between 2 days and 1 week
+
+==============================
+
+"#;
+        assert!(test_output.ends_with(target_tail), "{}", test_output);
+    }
+
+    #[test]
+    fn position_adjacency() {
+        let mut test_spread = gen_test_spread();
+        assert!(!test_spread.are_neighbours(0, 1));
+        test_spread.positions[1].x_pos = 0;
+        test_spread.positions[1].y_pos = -1;
+        assert!(test_spread.are_neighbours(0, 1));
+        test_spread.positions[1].y_pos = 0;
+        assert!(test_spread.are_neighbours(0, 1));
+        test_spread.positions[1].x_pos = 1;
+        test_spread.positions[1].y_pos = 1;
+        assert!(!test_spread.are_neighbours(0, 1));
+
+        test_spread.positions[1].neighbours = Some(vec![ 1 ]);
+        assert!(test_spread.are_neighbours(0, 1));
+        test_spread.positions[1].neighbours = Some(Vec::new());
+        test_spread.positions[1].x_pos = 0;
+        assert!(!test_spread.are_neighbours(0, 1));
+
+        let celtic_cross = Spread::new_from_json(include_str!("../default_files/included_spreads/celtic_cross.json"));
+        let index = |name: &str| celtic_cross.position_names().iter().position(|n| *n == name).unwrap();
+        assert!(celtic_cross.are_neighbours(index("The Present"), index("The Future")));
+        assert!(celtic_cross.are_neighbours(index("Outcome"), index("Hopes and Fears")));
+        assert!(!celtic_cross.are_neighbours(index("The Future"), index("External Influences")));
+    }
+
+    #[test]
+    fn filled_spread_dignities() {
+        let mut test_spread = gen_test_spread();
+        test_spread.positions[1].x_pos = 1;
+        test_spread.positions[1].y_pos = 0;
+        let deck_json = return_test_deck().replacen(
+            r#""name": "test deck","#,
+            r#""name": "test deck",
+            "summary": { "suit_elements": { "test_suit1": "Fire", "test_suit2": "Water" } },"#,
+            1
+            );
+        let mut test_deck = Deck::new_from_json(&deck_json);
+        let test_filled_spread = FilledSpread::new(
+            test_spread,
+            &mut test_deck,
+            1
+        );
+        let mut test_result = Vec::new();
+        let options = ReadingOptions {
+            dignities: true,
+            ..ReadingOptions::default()
+        };
+        test_filled_spread.print_with(false, 1, &options, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        let target_tail =
+r#"Elemental Dignities
+-------------------
+How each card is strengthened or weakened by the elements of its neighbours
+
+test position 1: test_name1 (Fire) is ill dignified
+  test position 2: test_name2 (Water) weakens it
+test position 2: test_name2 (Water) is ill dignified
+  test position 1: test_name1 (Fire) weakens it
+
+==============================
+
+"#;
+        assert!(test_output.ends_with(target_tail));
+    }
+
+    #[test]
+    fn filled_spread_trump_dignities() {
+        let mut test_spread = gen_test_spread();
+        test_spread.positions[1].x_pos = 1;
+        test_spread.positions[1].y_pos = 0;
+        let deck_json = return_test_deck()
+            .replacen(
+                r#""name": "test deck","#,
+                r#""name": "test deck",
+                "summary": { "suit_elements": { "test_suit2": "Water" } },"#,
+                1
+                )
+            .replacen(
+                r#""suit": "test_suit1","#,
+                &format!(r#""suit": "{}", "correspondences": {{ "element": "fire" }},"#, MAJOR_SUIT),
+                1
+                );
+        let mut test_deck = Deck::new_from_json(&deck_json);
+        let test_filled_spread = FilledSpread::new(
+            test_spread,
+            &mut test_deck,
+            1
+        );
+        let mut test_result = Vec::new();
+        let options = ReadingOptions {
+            dignities: true,
+            ..ReadingOptions::default()
+        };
+        test_filled_spread.print_with(false, 1, &options, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        assert!(test_output.contains("test position 1: test_name1 (Fire) is ill dignified\n"));
+        assert!(test_output.contains("  test position 1: test_name1 (Fire) weakens it\n"));
+    }
+
+    #[test]
+    fn filled_spread_combinations() {
+        let deck_json = return_test_deck().replacen(
+            r#""name": "test deck","#,
+            r#""name": "test deck",
+            "combinations": [
+                { "cards": ["test_name1", "TEST_NAME2"], "meaning": "pair meaning" },
+                { "cards": ["test_name1", "test_name2"], "meaning": "adjacent meaning", "adjacent": true },
+                { "cards": ["test_name", "test_name1"], "meaning": "missing meaning" }
+            ],"#,
+            1
+            );
+        let mut test_deck = Deck::new_from_json(&deck_json);
+        let test_filled_spread = FilledSpread::new(
+            gen_test_spread(),
+            &mut test_deck,
+            1
+        );
+        let found = test_filled_spread.found_combinations();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].meaning(), "pair meaning");
+
+        let mut test_result = Vec::new();
+        test_filled_spread.print(false, 1, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        let target_tail =
+r#"Combinations
+------------
+Cards in this reading that speak together
+
+test_name1 + TEST_NAME2
+pair meaning
+
+==============================
+
+"#;
+        assert!(test_output.ends_with(target_tail));
+
+        let mut adjacent_spread = gen_test_spread();
+        adjacent_spread.positions[1].x_pos = 0;
+        adjacent_spread.positions[1].y_pos = 1;
+        let mut test_deck = Deck::new_from_json(&deck_json);
+        let test_filled_spread = FilledSpread::new(
+            adjacent_spread,
+            &mut test_deck,
+            1
+        );
+        assert_eq!(test_filled_spread.found_combinations().len(), 2);
+    }
+
+    #[test]
+    fn narrative_fill() {
+        assert_eq!(
+            fill_template("{card} in {position}: {fortune}.", &[
+                ("card", "The Fool"),
+                ("position", "The Past"),
+                ("fortune", "What happened then?"),
+            ]),
+            "The Fool in The Past: What happened then?"
+        );
+        assert_eq!(
+            fill_template("Wait... {card}. {fortune}.. {other}!.", &[
+                ("card", "Two of Cups"),
+                ("fortune", "It ends."),
+            ]),
+            "Wait... Two of Cups. It ends.. {other}!."
+        );
+    }
+
+    #[test]
+    fn filled_spread_narrative() {
+        let mut test_spread = gen_test_spread();
+        test_spread.positions[1].narrative = Some("Finally {card} answers {meaning}: {fortune}.".to_string());
+        let mut test_deck = Deck::new_from_json(&return_test_deck());
+        let test_filled_spread = FilledSpread::new(
+            test_spread,
+            &mut test_deck,
+            1
+        );
+        let mut test_result = Vec::new();
+        let options = ReadingOptions {
+            narrative: true,
+            ..ReadingOptions::default()
+        };
+        test_filled_spread.print_with(false, 1, &options, &mut test_result);
+        let test_output = String::from_utf8(test_result).unwrap();
+        let target_output =
+r#"In test position 1, test_name1 appears. test_fortune1, with test_keyword1 at its heart.
+
+Finally test_name2 answers test meaning 2: test_fortune2.
+
+==============================
+
+"#;
+        assert_eq!(test_output, target_output);
+    }
+
     #[test]
     fn spread_deserial() {
         let test_spread: Spread = serde_json::from_str(&gen_test_spread_json()).unwrap();
         assert_eq!(gen_test_spread(), test_spread);
+        assert_eq!(test_spread.position_names(), vec!["test position 1", "test position 2"]);
     }
 
     #[test]
@@ -174,7 +1026,11 @@ Shadow: shadow_meaning2
         
         let manual_filled_spread: FilledSpread = FilledSpread {
             spread: gen_test_spread(),
+            jumpers: &[],
             cards: ref_deck.draw(2),
+            remaining: ref_deck.remaining(2),
+            notes: ref_deck.summary_notes(),
+            combinations: ref_deck.combinations(),
         };
 
         assert_eq!(
@@ -1,5 +1,6 @@
 #[cfg(test)]
 pub mod utils {
+use crate::deck::PositionCategory;
 use crate::spread::{
     Position,
     Spread,
@@ -18,7 +19,8 @@ pub fn gen_test_spread_json() -> String {
                   "meaning": "test meaning 1",
                   "orientation": "Horizontal",
                   "x_pos": 0,
-                  "y_pos": 0
+                  "y_pos": 0,
+                  "category": "past"
               },
               {
                   "order": 2,
@@ -41,6 +43,9 @@ pub fn gen_test_spread() -> Spread {
         orientation: PosOrientation::Horizontal,
         x_pos: 0,
         y_pos: 0,
+        narrative: None,
+        category: Some(PositionCategory::Past),
+        neighbours: None,
     };
     let pos2 = Position {
         order: 2,
@@ -49,12 +54,17 @@ pub fn gen_test_spread() -> Spread {
         orientation: PosOrientation::Vertical,
         x_pos: 5,
         y_pos: 5,
+        narrative: None,
+        category: None,
+        neighbours: None,
     };
     Spread {
         positions: vec![pos1, pos2],
         position_x_size: 5,
         position_y_size: 7,
-        name: "test spread".to_string()
+        name: "test spread".to_string(),
+        narrative: None,
+        meaning_strategy: None,
     }
 }
 }
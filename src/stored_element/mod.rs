@@ -12,27 +12,39 @@ pub trait StoredElement  {
     where 
         Self: std::marker::Sized + std::fmt::Display
     {
-        let mut found_items = Vec::new(); 
-        for e in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
-            if e.metadata().unwrap().is_file() {
-                found_items.push(Self::new_from_file(e.path()));
-            }
-        }
+        let mut found_items = Self::all_from_path(path);
 
         match found_items.len() {
             1 => {
-                return found_items.remove(0)
+                found_items.remove(0)
+            }
+            count if !atty::is(atty::Stream::Stdin) => {
+                panic!("Found {} choices under {} but no terminal to choose from, pass the path of one file", count, path.display())
             }
             _ => {
                 let mut menu = Select::new();
                 let selection = menu.items(&found_items[..])
                     .with_prompt("Make a selection:")
                     .interact();
-                return found_items.remove(
+                found_items.remove(
                     selection.expect("Error at menu select")
-                    );
+                    )
+            }
+        }
+    }
+
+    /// Every element stored under `path`, which can be a single file or a directory.
+    fn all_from_path(path: &Path) -> Vec<Self>
+    where
+        Self: std::marker::Sized
+    {
+        let mut found_items = Vec::new(); 
+        for e in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+            if e.metadata().unwrap().is_file() {
+                found_items.push(Self::new_from_file(e.path()));
             }
         }
+        found_items
     }
 
     fn new_from_file(path: &Path) -> Self 
@@ -55,3 +67,18 @@ pub trait StoredElement  {
         }
     }
 }
+
+/// Progress kept as a JSON file in the data directory, starting from the
+/// default value until the file is first saved.
+pub trait SavedState: serde::Serialize + serde::de::DeserializeOwned + Default {
+    fn load(path: &Path) -> std::io::Result<Self> {
+        match path.exists() {
+            true => Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?),
+            false => Ok(Self::default()),
+        }
+    }
+
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_string(self)?)
+    }
+}
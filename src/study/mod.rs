@@ -0,0 +1,282 @@
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::deck::{Card, Deck};
+use crate::stored_element::SavedState;
+
+pub const STUDY_FILENAME: &str = "study.json";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const STARTING_EASE: f64 = 2.5;
+const MINIMUM_EASE: f64 = 1.3;
+
+/// Which cards a study session draws from.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Focus {
+    All,
+    Majors,
+    Suit(String),
+}
+
+impl std::str::FromStr for Focus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Focus, String> {
+        match s.to_lowercase().as_str() {
+            "" => Err(String::from("Focus must name a suit, \"major\" or \"all\"")),
+            "all" => Ok(Focus::All),
+            "major" | "majors" => Ok(Focus::Majors),
+            suit => Ok(Focus::Suit(suit.to_string())),
+        }
+    }
+}
+
+impl Focus {
+    /// Fails for a suit the deck doesn't have, listing the suits it does.
+    pub fn check(&self, deck: &Deck) -> Result<(), String> {
+        let mut suits: Vec<&str> = Vec::new();
+        for card in deck.cards().iter().filter(|card| !card.is_major()) {
+            if !suits.iter().any(|suit| suit.eq_ignore_ascii_case(card.suit())) {
+                suits.push(card.suit());
+            }
+        }
+        match self {
+            Focus::Suit(suit) if !suits.iter().any(|s| s.eq_ignore_ascii_case(suit)) => Err(format!(
+                "{} has no suit \"{}\", choose from: {}, major or all",
+                deck.name,
+                suit,
+                suits.join(", ")
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn includes(&self, card: &Card) -> bool {
+        match self {
+            Focus::All => true,
+            Focus::Majors => card.is_major(),
+            Focus::Suit(suit) => card.suit().eq_ignore_ascii_case(suit),
+        }
+    }
+}
+
+/// How well a card was remembered, from forgotten to effortless.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Grade {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl Grade {
+    pub const ALL: [Grade; 4] = [ Grade::Again, Grade::Hard, Grade::Good, Grade::Easy ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Grade::Again => "Again - I didn't know it",
+            Grade::Hard => "Hard - I got there in the end",
+            Grade::Good => "Good - I knew it",
+            Grade::Easy => "Easy - I knew it at once",
+        }
+    }
+
+    /// The SM-2 quality score for the grade.
+    fn quality(self) -> f64 {
+        match self {
+            Grade::Again => 1.0,
+            Grade::Hard => 3.0,
+            Grade::Good => 4.0,
+            Grade::Easy => 5.0,
+        }
+    }
+}
+
+/// Spaced repetition schedule for one card, following SM-2.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Review {
+    pub ease: f64,
+    pub interval: u32,
+    pub repetitions: u32,
+    pub due: String,
+}
+
+impl Review {
+    fn new(today: NaiveDate) -> Review {
+        Review {
+            ease: STARTING_EASE,
+            interval: 0,
+            repetitions: 0,
+            due: today.format(DATE_FORMAT).to_string(),
+        }
+    }
+
+    pub fn is_due(&self, today: NaiveDate) -> bool {
+        match NaiveDate::parse_from_str(&self.due, DATE_FORMAT) {
+            Ok(due) => due <= today,
+            Err(_) => true,
+        }
+    }
+
+    pub fn grade(&mut self, grade: Grade, today: NaiveDate) {
+        let quality = grade.quality();
+        match grade {
+            Grade::Again => {
+                self.repetitions = 0;
+                self.interval = 1;
+            },
+            _ => {
+                self.interval = match self.repetitions {
+                    0 => 1,
+                    1 => 6,
+                    _ => (self.interval as f64 * self.ease).round() as u32,
+                };
+                self.repetitions += 1;
+            },
+        }
+        self.ease = (self.ease + 0.1 - (5.0 - quality) * (0.08 + (5.0 - quality) * 0.02)).max(MINIMUM_EASE);
+        self.due = (today + Duration::days(self.interval as i64)).format(DATE_FORMAT).to_string();
+    }
+}
+
+/// Review schedules for every card studied so far, keyed by deck and card name.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+pub struct StudyState {
+    reviews: HashMap<String, Review>,
+}
+
+fn card_key(deck: &Deck, card: &Card) -> String {
+    format!("{}/{}", deck.name, card.name())
+}
+
+impl SavedState for StudyState {}
+
+impl StudyState {
+    pub fn review(&self, deck: &Deck, card: &Card) -> Option<&Review> {
+        self.reviews.get(&card_key(deck, card))
+    }
+
+    /// Cards in focus that are due today, overdue cards first, followed by
+    /// at most `new_limit` cards that have never been studied.
+    pub fn due_cards<'d>(&self, deck: &'d Deck, focus: &Focus, today: NaiveDate, new_limit: usize) -> Vec<&'d Card> {
+        let in_focus = deck.cards().iter().filter(|card| focus.includes(card));
+        let mut due: Vec<&Card> = in_focus.clone()
+            .filter(|card| matches!(self.review(deck, card), Some(r) if r.is_due(today)))
+            .collect();
+        due.sort_by_key(|card| self.review(deck, card).map(|r| r.due.clone()));
+        due.extend(in_focus.filter(|card| self.review(deck, card).is_none()).take(new_limit));
+        due
+    }
+
+    pub fn grade(&mut self, deck: &Deck, card: &Card, grade: Grade, today: NaiveDate) {
+        self.reviews.entry(card_key(deck, card))
+            .or_insert_with(|| Review::new(today))
+            .grade(grade, today);
+    }
+}
+
+/// A flashcard: the prompt shown to the student and the answer revealed after.
+#[derive(PartialEq, Debug)]
+pub struct Question {
+    pub prompt: String,
+    pub answer: String,
+}
+
+impl Question {
+    /// Asks for the card's keywords, a light or shadow meaning, or which card
+    /// a keyword belongs to, chosen by the seed.
+    pub fn new(card: &Card, seed: u64) -> Question {
+        use rand::{Rng, SeedableRng};
+        use rand::rngs;
+
+        let mut rng = rngs::StdRng::seed_from_u64(seed);
+        let kinds = match card.keywords().is_empty() {
+            true => 2,
+            false => 4,
+        };
+        match rng.gen_range(0, kinds) {
+            0 => Question {
+                prompt: format!("Give a light meaning of {}", card.name()),
+                answer: card.lights().join("\n"),
+            },
+            1 => Question {
+                prompt: format!("Give a shadow meaning of {}", card.name()),
+                answer: card.shadows().join("\n"),
+            },
+            2 => Question {
+                prompt: format!("What are the keywords of {}?", card.name()),
+                answer: card.keywords().join(", "),
+            },
+            _ => Question {
+                prompt: format!("Which card has the keyword \"{}\"?", card.keyword(seed)),
+                answer: card.name().to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use crate::study::{Focus, Grade, Question, Review, StudyState};
+    use crate::deck::Deck;
+    use crate::deck::test_utils::utils::{return_test_card, return_test_deck};
+    use crate::stored_element::{SavedState, StoredElement};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    #[test]
+    fn review_schedule() {
+        let mut review = Review::new(date(1));
+        review.grade(Grade::Good, date(1));
+        assert_eq!((review.interval, review.due.as_str()), (1, "2024-03-02"));
+        review.grade(Grade::Good, date(2));
+        assert_eq!((review.interval, review.due.as_str()), (6, "2024-03-08"));
+        review.grade(Grade::Easy, date(8));
+        assert_eq!(review.interval, 15);
+        assert!(review.ease > 2.5);
+        assert!(!review.is_due(date(22)));
+        assert!(review.is_due(date(23)));
+
+        review.grade(Grade::Again, date(23));
+        assert_eq!((review.interval, review.repetitions), (1, 0));
+        assert!(review.ease < 2.6);
+    }
+
+    #[test]
+    fn study_due_cards() {
+        let deck = Deck::new_from_json(&return_test_deck());
+        let mut state = StudyState::default();
+        let names = |cards: Vec<&crate::deck::Card>| cards.iter().map(|c| c.name().to_string()).collect::<Vec<String>>();
+
+        assert_eq!(names(state.due_cards(&deck, &Focus::All, date(1), 2)), vec!["test_name", "test_name1"]);
+        assert_eq!(names(state.due_cards(&deck, &"TEST_SUIT2".parse().unwrap(), date(1), 20)), vec!["test_name2"]);
+        assert!("TEST_SUIT2".parse::<Focus>().unwrap().check(&deck).is_ok());
+        assert!(Focus::Majors.check(&deck).is_ok());
+        let unknown = "cpus".parse::<Focus>().unwrap().check(&deck).unwrap_err();
+        assert!(unknown.contains("no suit \"cpus\""), "{}", unknown);
+        assert!(unknown.contains("test_suit1, test_suit2"), "{}", unknown);
+
+        state.grade(&deck, &deck.cards()[0], Grade::Good, date(1));
+        assert_eq!(names(state.due_cards(&deck, &Focus::All, date(1), 20)), vec!["test_name1", "test_name2"]);
+        assert_eq!(names(state.due_cards(&deck, &Focus::All, date(2), 0)), vec!["test_name"]);
+
+        let mut path = std::env::temp_dir();
+        path.push(format!("terminal_tarot_study_{}.json", std::process::id()));
+        state.save(&path).unwrap();
+        assert_eq!(StudyState::load(&path).unwrap(), state);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn study_questions() {
+        let card = return_test_card();
+        let prompts: Vec<String> = (0..20).map(|seed| Question::new(&card, seed).prompt).collect();
+        assert!(prompts.contains(&"What are the keywords of test_name?".to_string()));
+        assert!(prompts.iter().any(|p| p.starts_with("Which card has the keyword")));
+        assert!(prompts.contains(&"Give a shadow meaning of test_name".to_string()));
+        assert_eq!(Question::new(&card, 1), Question::new(&card, 1));
+    }
+}
@@ -0,0 +1,245 @@
+use serde::{Deserialize, Deserializer};
+use std::collections::{BTreeMap, HashMap};
+use crate::deck::Card;
+use crate::template::{RankCountModel, SuitCountModel, SummaryModel};
+use crate::timing::TimingUnit;
+
+/// Suit elements, timing units, labels and pattern notes used by the reading summary.
+/// Decks can supply their own under a `summary` key to localize them.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct SummaryNotes {
+    #[serde(default)]
+    suit_elements: HashMap<String, String>,
+    #[serde(default)]
+    court_ranks: Vec<u8>,
+    #[serde(default = "bundled_labels")]
+    labels: HashMap<String, String>,
+    #[serde(default)]
+    major_majority: Option<String>,
+    #[serde(default)]
+    minor_majority: Option<String>,
+    #[serde(default)]
+    suit_majority: HashMap<String, String>,
+    #[serde(default)]
+    court_majority: Option<String>,
+    #[serde(default)]
+    reversal_majority: Option<String>,
+    #[serde(default)]
+    repeated_rank: HashMap<String, String>,
+    #[serde(default)]
+    timing_units: HashMap<String, TimingUnit>,
+}
+
+const BUNDLED_NOTES: &str = include_str!("../default_files/included_notes/summary_notes.json");
+
+impl Default for SummaryNotes {
+    fn default() -> SummaryNotes {
+        serde_json::from_str(BUNDLED_NOTES).expect("Failed to deserialize bundled summary notes")
+    }
+}
+
+fn bundled_labels() -> HashMap<String, String> {
+    SummaryNotes::default().labels
+}
+
+/// Reads a deck's `summary`, keeping the bundled value of every field the
+/// deck leaves out. Labels are merged key by key.
+pub fn deserialize_over_bundled<'de, D>(deserializer: D) -> Result<SummaryNotes, D::Error>
+where
+    D: Deserializer<'de>
+{
+    use serde::de::Error;
+    use serde_json::{Map, Value};
+
+    let mut fields: Map<String, Value> = serde_json::from_str(BUNDLED_NOTES)
+        .expect("Failed to deserialize bundled summary notes");
+    let mut overrides = Map::deserialize(deserializer)?;
+    if let Some(labels) = overrides.remove("labels") {
+        match (fields.get_mut("labels"), labels) {
+            (Some(Value::Object(bundled)), Value::Object(labels)) => bundled.extend(labels),
+            (_, labels) => {
+                overrides.insert(String::from("labels"), labels);
+            },
+        }
+    }
+    fields.extend(overrides);
+    serde_json::from_value(Value::Object(fields)).map_err(D::Error::custom)
+}
+
+impl SummaryNotes {
+    pub fn element(&self, suit: &str) -> Option<&str> {
+        self.suit_elements.get(suit).map(|element| element.as_str())
+    }
+
+    pub fn timing_unit(&self, suit: &str) -> Option<&TimingUnit> {
+        self.timing_units.get(suit)
+    }
+
+    pub fn is_court(&self, rank: u8) -> bool {
+        self.court_ranks.contains(&rank)
+    }
+
+    pub fn labels(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ReadingSummary {
+    major_count: usize,
+    minor_count: usize,
+    suit_counts: BTreeMap<String, usize>,
+    court_count: usize,
+    reversed_count: usize,
+    repeated_ranks: BTreeMap<u8, usize>,
+}
+
+impl ReadingSummary {
+    pub fn new(cards: &[Card], notes: &SummaryNotes) -> ReadingSummary {
+        let mut suit_counts = BTreeMap::new();
+        let mut rank_counts = BTreeMap::new();
+        for card in cards.iter().filter(|card| !card.is_major()) {
+            *suit_counts.entry(card.suit().to_string()).or_insert(0) += 1;
+            *rank_counts.entry(card.rank()).or_insert(0) += 1;
+        }
+        rank_counts.retain(|_, count| *count > 1);
+
+        let major_count = cards.iter().filter(|card| card.is_major()).count();
+        ReadingSummary {
+            major_count,
+            minor_count: cards.len() - major_count,
+            suit_counts,
+            court_count: cards.iter()
+                .filter(|card| !card.is_major() && notes.is_court(card.rank()))
+                .count(),
+            reversed_count: cards.iter().filter(|card| card.is_reversed()).count(),
+            repeated_ranks: rank_counts,
+        }
+    }
+
+    fn is_majority(&self, count: usize) -> bool {
+        count * 2 > self.major_count + self.minor_count
+    }
+
+    /// Pattern notes that apply to this summary, in display order.
+    pub fn notes<'n>(&self, notes: &'n SummaryNotes) -> Vec<&'n str> {
+        let mut found = Vec::new();
+        if self.is_majority(self.major_count) {
+            found.extend(notes.major_majority.as_deref());
+        } else if self.is_majority(self.minor_count) {
+            found.extend(notes.minor_majority.as_deref());
+        }
+        for (suit, count) in self.suit_counts.iter() {
+            if self.is_majority(*count) {
+                found.extend(notes.suit_majority.get(suit).map(|note| note.as_str()));
+            }
+        }
+        if self.is_majority(self.court_count) {
+            found.extend(notes.court_majority.as_deref());
+        }
+        if self.is_majority(self.reversed_count) {
+            found.extend(notes.reversal_majority.as_deref());
+        }
+        for rank in self.repeated_ranks.keys() {
+            found.extend(notes.repeated_rank.get(&rank.to_string()).map(|note| note.as_str()));
+        }
+        found
+    }
+
+    pub fn to_model(&self, notes: &SummaryNotes) -> SummaryModel {
+        SummaryModel {
+            cards: self.major_count + self.minor_count,
+            major_count: self.major_count,
+            minor_count: self.minor_count,
+            suits: self.suit_counts.iter()
+                .map(|(suit, count)| SuitCountModel {
+                    suit: suit.clone(),
+                    count: *count,
+                    element: notes.element(suit).map(String::from),
+                })
+                .collect(),
+            court_count: self.court_count,
+            reversed_count: self.reversed_count,
+            repeated_ranks: self.repeated_ranks.iter()
+                .map(|(rank, count)| RankCountModel { rank: *rank, count: *count })
+                .collect(),
+            notes: self.notes(notes).into_iter().map(String::from).collect(),
+            labels: notes.labels().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deck::Deck;
+    use crate::deck::test_utils::utils::return_test_deck;
+    use crate::stored_element::StoredElement;
+    use crate::summary::{ReadingSummary, SummaryNotes};
+    use crate::template::ReadingTemplate;
+
+    fn gen_test_notes() -> SummaryNotes {
+        serde_json::from_str(r#"
+        {
+            "suit_elements": { "test_suit": "Fire" },
+            "court_ranks": [2],
+            "minor_majority": "mostly minor",
+            "suit_majority": { "test_suit1": "mostly suit1" },
+            "repeated_rank": { "1": "many ones" }
+        }
+        "#).unwrap()
+    }
+
+    #[test]
+    fn bundled_notes_deserial() {
+        let notes = SummaryNotes::default();
+        assert_eq!(notes.element("cups"), Some("Water"));
+        assert_eq!(notes.court_ranks, vec![11, 12, 13, 14]);
+    }
+
+    #[test]
+    fn partial_deck_notes() {
+        let test_deck = Deck::new_from_json(&return_test_deck().replace(
+            r#""name": "test deck","#,
+            r#""name": "test deck", "summary": {
+                "minor_majority": "surtout mineures",
+                "labels": { "reversed_cards": "Cartes renversées" }
+            },"#
+            ));
+        let notes = test_deck.summary_notes();
+        assert_eq!(notes.minor_majority.as_deref(), Some("surtout mineures"));
+        assert_eq!(notes.labels()["reversed_cards"], "Cartes renversées");
+        assert_eq!(notes.labels()["court_cards"], "Court cards");
+        assert_eq!(notes.element("coins"), Some("Earth"));
+        assert!(notes.is_court(14));
+        assert!(notes.timing_unit("cups").is_some());
+    }
+
+    #[test]
+    fn summary_counts() {
+        let test_deck = Deck::new_from_json(&return_test_deck());
+        let notes = gen_test_notes();
+        let summary = ReadingSummary::new(test_deck.draw(3), &notes);
+        assert_eq!(summary.minor_count, 3);
+        assert_eq!(summary.major_count, 0);
+        assert_eq!(summary.court_count, 1);
+        assert!(summary.repeated_ranks.is_empty());
+        assert_eq!(summary.notes(&notes), vec!["mostly minor"]);
+    }
+
+    #[test]
+    fn summary_print() {
+        let test_deck = Deck::new_from_json(&return_test_deck());
+        let notes = gen_test_notes();
+        let summary = ReadingSummary::new(&test_deck.draw(3)[1..2], &notes);
+        let target_body = r#"
+Major arcana: 0, Minor arcana: 1
+Suits: test_suit1 1
+Court cards: 0
+Reversed cards: 0
+* mostly minor
+* mostly suit1
+"#;
+        let rendered = ReadingTemplate::default().render("summary", &summary.to_model(&notes));
+        assert!(rendered.contains(target_body), "{}", rendered);
+    }
+}
@@ -0,0 +1,244 @@
+use handlebars::{handlebars_helper, Handlebars};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::deck::{Correspondences, PositionCategory};
+use crate::dignities::{Dignity, Element};
+
+/// Written by the `pause` helper wherever an interactive reading should wait for ENTER.
+pub const PAUSE_MARKER: &str = "\u{0}pause\u{0}";
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct CardModel {
+    pub name: String,
+    pub title: String,
+    pub suit: String,
+    pub rank: u8,
+    pub reversed: bool,
+    pub fortune: String,
+    pub light: String,
+    pub shadow: String,
+    pub fortunes: Vec<String>,
+    pub lights: Vec<String>,
+    pub shadows: Vec<String>,
+    pub keyword: String,
+    pub keywords: Vec<String>,
+    pub correspondences: Option<Correspondences>,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct PositionModel {
+    pub order: u8,
+    pub name: String,
+    pub meaning: String,
+    pub category: Option<PositionCategory>,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct PlacementModel {
+    #[serde(flatten)]
+    pub position: PositionModel,
+    pub narrative: String,
+    pub card: CardModel,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct CombinationModel {
+    pub cards: Vec<String>,
+    pub meaning: String,
+    pub adjacent: bool,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct SuitCountModel {
+    pub suit: String,
+    pub count: usize,
+    pub element: Option<String>,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct RankCountModel {
+    pub rank: u8,
+    pub count: usize,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct SummaryModel {
+    pub cards: usize,
+    pub major_count: usize,
+    pub minor_count: usize,
+    pub suits: Vec<SuitCountModel>,
+    pub court_count: usize,
+    pub reversed_count: usize,
+    pub repeated_ranks: Vec<RankCountModel>,
+    pub notes: Vec<String>,
+    pub labels: HashMap<String, String>,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct NeighbourModel {
+    pub position: String,
+    pub card: String,
+    pub element: Option<Element>,
+    pub dignity: Dignity,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct DignityModel {
+    pub position: String,
+    pub card: String,
+    pub element: Option<Element>,
+    pub dignity: Dignity,
+    pub neighbours: Vec<NeighbourModel>,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct TimingEntryModel {
+    pub position: String,
+    pub card: String,
+    pub estimate: String,
+    pub days: u32,
+}
+
+/// Timing for each numbered card, with the range they cover. `latest` is
+/// left empty when every estimate comes to the same number of days.
+#[derive(Serialize, PartialEq, Debug)]
+pub struct TimingModel {
+    pub entries: Vec<TimingEntryModel>,
+    pub soonest: Option<String>,
+    pub latest: Option<String>,
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct RemainingModel {
+    pub count: usize,
+    pub majors: Vec<String>,
+    pub suits: Vec<SuitCountModel>,
+}
+
+/// Everything a reading template can show. Sections that were not asked for
+/// are left empty so templates can test for them with `{{#if}}`.
+#[derive(Serialize, PartialEq, Debug)]
+pub struct ReadingModel {
+    pub question: Option<String>,
+    pub spread: String,
+    pub seed: u64,
+    pub date: String,
+    pub jumpers: Vec<CardModel>,
+    pub positions: Vec<PlacementModel>,
+    pub narrative: bool,
+    pub narrative_intro: String,
+    pub narrative_outro: String,
+    pub combinations: Vec<CombinationModel>,
+    pub dignities: Option<Vec<DignityModel>>,
+    pub summary: Option<SummaryModel>,
+    pub timing: Option<TimingModel>,
+    pub shadow_card: Option<CardModel>,
+    pub remaining: Option<RemainingModel>,
+}
+
+handlebars_helper!(underline: |text: str, fill: str| fill.repeat(text.chars().count()));
+handlebars_helper!(join: |items: array, separator: str| items.iter()
+    .map(|item| item.as_str().unwrap_or_default())
+    .collect::<Vec<&str>>()
+    .join(separator));
+handlebars_helper!(pause: |*_args| PAUSE_MARKER);
+
+pub struct ReadingTemplate {
+    registry: Handlebars<'static>,
+}
+
+impl Default for ReadingTemplate {
+    fn default() -> ReadingTemplate {
+        let mut registry = Handlebars::new();
+        registry.register_escape_fn(handlebars::no_escape);
+        registry.register_helper("underline", Box::new(underline));
+        registry.register_helper("join", Box::new(join));
+        registry.register_helper("pause", Box::new(pause));
+        let bundled = [
+            ("card", include_str!("../default_files/included_templates/card.hbs")),
+            ("correspondences", include_str!("../default_files/included_templates/correspondences.hbs")),
+            ("dignities", include_str!("../default_files/included_templates/dignities.hbs")),
+            ("info", include_str!("../default_files/included_templates/info.hbs")),
+            ("position", include_str!("../default_files/included_templates/position.hbs")),
+            ("reading", include_str!("../default_files/included_templates/reading.hbs")),
+            ("remaining", include_str!("../default_files/included_templates/remaining.hbs")),
+            ("summary", include_str!("../default_files/included_templates/summary.hbs")),
+            ("timing", include_str!("../default_files/included_templates/timing.hbs")),
+        ];
+        for (name, contents) in bundled.iter() {
+            registry.register_template_string(name, contents).expect("Failed to parse bundled template");
+        }
+        ReadingTemplate { registry }
+    }
+}
+
+impl ReadingTemplate {
+    /// Loads user templates over the bundled ones. A single file replaces the
+    /// `reading` template; in a directory each `<name>.hbs` replaces or adds
+    /// the template of that name, e.g. `card.hbs` or `position.hbs`.
+    pub fn new_from_path(path: &Path) -> ReadingTemplate {
+        let mut template = ReadingTemplate::default();
+        if path.is_file() {
+            template.registry.register_template_file("reading", path).expect("Failed to parse template file");
+            return template;
+        }
+        for e in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+            let is_template = matches!(e.path().extension(), Some(ext) if ext == "hbs");
+            if e.metadata().unwrap().is_file() && is_template {
+                let name = e.path().file_stem().unwrap().to_string_lossy().to_string();
+                template.registry.register_template_file(&name, e.path()).expect("Failed to parse template file");
+            }
+        }
+        template
+    }
+
+    pub fn render(&self, name: &str, data: &impl Serialize) -> String {
+        self.registry.render(name, data).expect("Failed to render template")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::template::{
+        CombinationModel,
+        ReadingTemplate,
+        PAUSE_MARKER
+    };
+
+    #[test]
+    fn template_helpers() {
+        let mut template = ReadingTemplate::default();
+        template.registry.register_template_string(
+            "test",
+            "{{underline title \"=\"}}|{{join cards \" + \"}}|{{pause}}"
+            ).unwrap();
+        let combination = CombinationModel {
+            cards: vec![ "a".to_string(), "b".to_string() ],
+            meaning: String::new(),
+            adjacent: false,
+        };
+        let data = serde_json::json!({ "title": "four", "cards": combination.cards });
+        assert_eq!(
+            template.render("test", &data),
+            format!("====|a + b|{}", PAUSE_MARKER)
+            );
+    }
+
+    #[test]
+    fn template_from_directory() {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("terminal_tarot_template_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("card.hbs"), "[{{title}}]").unwrap();
+        std::fs::write(dir.join("notes.txt"), "{{#if").unwrap();
+
+        let template = ReadingTemplate::new_from_path(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let data = serde_json::json!({ "title": "The Fool" });
+        assert_eq!(template.render("card", &data), "[The Fool]");
+        assert_eq!(template.render("position", &serde_json::json!({ "name": "ab", "meaning": "m" })), "ab\n--\nm\n");
+    }
+}
@@ -0,0 +1,87 @@
+use serde::Deserialize;
+
+use crate::deck::Card;
+use crate::summary::SummaryNotes;
+
+/// The unit of time a suit counts in, with its length in days so
+/// estimates in different units can be compared.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct TimingUnit {
+    singular: String,
+    plural: String,
+    days: u32,
+}
+
+/// How long a single numbered card says the matter will take.
+#[derive(PartialEq, Debug)]
+pub struct TimingEstimate<'a> {
+    pub card: &'a Card,
+    pub count: u8,
+    pub unit: &'a TimingUnit,
+}
+
+impl TimingEstimate<'_> {
+    pub fn days(&self) -> u32 {
+        self.count as u32 * self.unit.days
+    }
+
+    /// The estimate in words, e.g. "3 weeks".
+    pub fn describe(&self) -> String {
+        match self.count {
+            1 => format!("1 {}", self.unit.singular),
+            count => format!("{} {}", count, self.unit.plural),
+        }
+    }
+}
+
+/// Estimates for each numbered minor arcana card whose suit has a timing
+/// unit, in the order given. Major arcana and court cards give no timing.
+pub fn estimate<'a>(cards: &'a [Card], notes: &'a SummaryNotes) -> Vec<TimingEstimate<'a>> {
+    cards.iter()
+        .filter(|card| !card.is_major() && card.rank() > 0 && !notes.is_court(card.rank()))
+        .filter_map(|card| notes.timing_unit(card.suit()).map(|unit| TimingEstimate {
+            card,
+            count: card.rank(),
+            unit,
+        }))
+        .collect()
+}
+
+/// The soonest and latest of the estimates, if there are any.
+pub fn range<'e, 'a>(estimates: &'e [TimingEstimate<'a>]) -> Option<(&'e TimingEstimate<'a>, &'e TimingEstimate<'a>)> {
+    let soonest = estimates.iter().min_by_key(|estimate| estimate.days())?;
+    let latest = estimates.iter().max_by_key(|estimate| estimate.days())?;
+    Some((soonest, latest))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::timing::{estimate, range};
+    use crate::deck::Deck;
+    use crate::deck::test_utils::utils::return_test_deck;
+    use crate::stored_element::StoredElement;
+    use crate::summary::SummaryNotes;
+
+    #[test]
+    fn timing_estimates() {
+        let deck = Deck::new_from_json(&return_test_deck());
+        let notes: SummaryNotes = serde_json::from_str(r#"{
+            "timing_units": {
+                "test_suit": { "singular": "hour", "plural": "hours", "days": 0 },
+                "test_suit1": { "singular": "week", "plural": "weeks", "days": 7 },
+                "test_suit2": { "singular": "day", "plural": "days", "days": 1 }
+            }
+        }"#).unwrap();
+        let estimates = estimate(deck.cards(), &notes);
+        let described: Vec<String> = estimates.iter().map(|estimate| estimate.describe()).collect();
+        assert_eq!(described, vec!["1 week", "2 days"]);
+
+        let (soonest, latest) = range(&estimates).unwrap();
+        assert_eq!((soonest.card.name(), latest.card.name()), ("test_name2", "test_name1"));
+        assert!(range(&estimates[..0]).is_none());
+
+        let bundled = SummaryNotes::default();
+        assert_eq!(bundled.timing_unit("swords").unwrap().plural, "weeks");
+        assert!(estimate(deck.cards(), &bundled).is_empty());
+    }
+}